@@ -0,0 +1,19 @@
+// `Client::translate_batch()` sends many texts in as few requests as possible, and returns one result per text.
+
+use libretranslate::{Client, Language};
+
+#[tokio::main]
+async fn main() {
+    let client = Client::new("https://libretranslate.de/")
+        .from_lang(Language::English)
+        .to_lang(Language::Spanish);
+
+    let inputs = ["Open", "Save", "Save as...", "Close"];
+
+    for result in client.translate_batch(&inputs).await {
+        match result {
+            Ok(data) => println!("{} => {}", data.input, data.output),
+            Err(error) => println!("Error: {}", error),
+        }
+    }
+}
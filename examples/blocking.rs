@@ -0,0 +1,23 @@
+// With the `blocking` feature, translations can be made without an async runtime.
+
+use libretranslate::{blocking, Language, Translate, TranslationBuilder};
+
+fn main() {
+    let data =
+        blocking::translate(Language::English, Language::French, "Hello world!", None).unwrap();
+    println!("Output: \"{}\"", data.output);
+
+    let data = TranslationBuilder::new()
+        .from_lang(Language::English)
+        .to_lang(Language::German)
+        .text("Hello world!")
+        .translate_blocking()
+        .unwrap();
+    println!("Output: \"{}\"", data.output);
+
+    let text = "Hello world!"
+        .to_lang(Language::Spanish)
+        .translate_blocking()
+        .unwrap();
+    println!("Output: \"{}\"", text);
+}
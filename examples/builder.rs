@@ -7,10 +7,15 @@ async fn main() {
         .from_lang(Language::French)
         .to_lang(Language::Italian)
         .url("https://libretranslate.de/")
+        .alternatives(3)
         // .key("YOUR-OWN-KEY")
         .translate()
         .await
         .unwrap();
 
     println!("{}", builder.output);
+
+    for alternative in builder.alternatives {
+        println!("or: {}", alternative);
+    }
 }
@@ -0,0 +1,30 @@
+// A `Client` keeps the URL, key and default languages for an instance, and reuses its connections between requests.
+
+use libretranslate::{Client, Language, Translate};
+
+#[tokio::main]
+async fn main() {
+    let client = Client::new("https://libretranslate.de/")
+        // .key("YOUR-OWN-KEY")
+        .from_lang(Language::English)
+        .to_lang(Language::French);
+
+    let data = client.translate("Hello world!").await.unwrap();
+    println!("Output {}: {}", data.target.as_pretty(), data.output);
+
+    let data = client
+        .text("Hello world!")
+        .to_lang(Language::German)
+        .translate()
+        .await
+        .unwrap();
+    println!("Output {}: {}", data.target.as_pretty(), data.output);
+
+    let text = "Hello world!"
+        .to_lang(Language::Spanish)
+        .client(&client)
+        .translate()
+        .await
+        .unwrap();
+    println!("Output: {}", text);
+}
@@ -11,4 +11,8 @@ async fn main() {
 
     println!("Input {}: {}", data.source.as_pretty(), data.input);
     println!("Output {}: {}", data.target.as_pretty(), data.output);
+
+    if let Some(confidence) = data.confidence {
+        println!("Confidence: {}", confidence);
+    }
 }
@@ -0,0 +1,16 @@
+// A `Client` can fall back to other instances when its own is unreachable or overloaded.
+
+use libretranslate::{Client, Instance, Language};
+
+#[tokio::main]
+async fn main() {
+    let client = Client::new("http://localhost:5000/")
+        .fallback(Instance::new("https://libretranslate.de/"))
+        .fallback(Instance::new("https://libretranslate.com/").key("YOUR-OWN-KEY"))
+        .from_lang(Language::English)
+        .to_lang(Language::French);
+
+    let data = client.translate("Hello world!").await.unwrap();
+    println!("Output {}: {}", data.target.as_pretty(), data.output);
+    println!("Served by {}", data.url);
+}
@@ -0,0 +1,26 @@
+// USAGE:
+// $ ./file <INPUT FILE> <OUTPUT FILE>
+
+// `Client::translate_file_to()` uploads a document, and writes the translated one next to it.
+
+use libretranslate::{Client, Language};
+
+#[tokio::main]
+async fn main() {
+    let args = &std::env::args().collect::<Vec<_>>();
+
+    if args.len() != 3 {
+        eprintln!("FORMAT: <INPUT FILE> <OUTPUT FILE>");
+        std::process::exit(1);
+    };
+
+    let client = Client::new("https://libretranslate.de/");
+
+    match client
+        .translate_file_to(&args[1], &args[2], Language::English, Language::French)
+        .await
+    {
+        Ok(()) => println!("Translated {} into {}", args[1], args[2]),
+        Err(error) => println!("Error: {}", error),
+    };
+}
@@ -0,0 +1,18 @@
+// With `Format::Html` the markup is left untouched and only the text inside it is translated.
+
+use libretranslate::{Format, Language, TranslationBuilder};
+
+#[tokio::main]
+async fn main() {
+    let data = TranslationBuilder::new()
+        .text("<p>Hello <b>world</b>!</p>")
+        .from_lang(Language::English)
+        .to_lang(Language::French)
+        .format(Format::Html)
+        .url("https://libretranslate.de/")
+        .translate()
+        .await
+        .unwrap();
+
+    println!("{}", data.output);
+}
@@ -0,0 +1,18 @@
+// `Client::info()` tells what an instance supports, the client also uses it to apply the instance's character limit.
+
+use libretranslate::Client;
+
+#[tokio::main]
+async fn main() {
+    let client = Client::new("https://libretranslate.de/");
+
+    let info = client.info().await.unwrap();
+
+    match info.char_limit {
+        Some(limit) => println!("Character limit: {}", limit),
+        None => println!("Character limit: none"),
+    };
+    println!("Key required: {}", info.key_required);
+    println!("Suggestions: {}", info.suggestions);
+    println!("Files: {}", info.supported_files_format.join(", "));
+}
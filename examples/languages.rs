@@ -0,0 +1,17 @@
+// `Client::languages()` lists what the instance actually serves, and which targets each language can be translated to.
+
+use libretranslate::Client;
+
+#[tokio::main]
+async fn main() {
+    let client = Client::new("https://libretranslate.de/");
+
+    for language in client.languages().await.unwrap() {
+        println!(
+            "{} ({}): {}",
+            language.name,
+            language.code,
+            language.targets.join(", ")
+        );
+    }
+}
@@ -0,0 +1,28 @@
+// `Client::pseudo()` makes fake translations without a server, to check how a user interface copes with longer,
+// accented or right-to-left text.
+
+use libretranslate::{Client, Language, Pseudo};
+
+#[tokio::main]
+async fn main() {
+    let client = Client::pseudo(Pseudo::new().expansion(0.4)).from_lang(Language::English);
+
+    let inputs = [
+        "Open",
+        "Save as...",
+        "Welcome back, {name}!",
+        "%d files selected",
+    ];
+
+    for target in [Language::French, Language::Arabic] {
+        for result in client
+            .clone()
+            .to_lang(target)
+            .translate_batch(&inputs)
+            .await
+        {
+            let data = result.unwrap();
+            println!("{} => {}", data.input, data.output);
+        }
+    }
+}
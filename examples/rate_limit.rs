@@ -0,0 +1,31 @@
+// A `RateLimit` makes many concurrent translations wait for their turn instead of being refused by the instance.
+
+use futures_util::future::join_all;
+use libretranslate::{Client, Language, RateLimit, Translate};
+
+#[tokio::main]
+async fn main() {
+    let limit = RateLimit::new()
+        .requests_per_second(2.0)
+        .chars_per_minute(10_000);
+
+    let client = Client::new("https://libretranslate.de/").rate_limit(limit);
+
+    let texts = [
+        "Hello world!",
+        "Good morning!",
+        "How are you?",
+        "See you later!",
+    ];
+
+    let queries = texts.iter().map(|text| {
+        text.from_lang(Language::English)
+            .to_lang(Language::French)
+            .client(&client)
+            .translate()
+    });
+
+    for text in join_all(queries).await {
+        println!("Output: {}", text.unwrap());
+    }
+}
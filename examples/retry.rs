@@ -0,0 +1,23 @@
+// Public instances sometimes fail or stall for a moment, a `RetryPolicy` sends the request again after a short wait
+// and timeouts make sure a stalled request doesn't hang forever.
+
+use libretranslate::{Client, Language, RetryPolicy};
+use std::time::Duration;
+
+#[tokio::main]
+async fn main() {
+    let policy = RetryPolicy::new()
+        .max_attempts(5)
+        .base_delay(Duration::from_millis(250))
+        .max_delay(Duration::from_secs(10));
+
+    let client = Client::new("https://libretranslate.de/")
+        .from_lang(Language::English)
+        .to_lang(Language::French)
+        .connect_timeout(Duration::from_secs(5))
+        .timeout(Duration::from_secs(20))
+        .retry(policy);
+
+    let data = client.translate("Hello world!").await.unwrap();
+    println!("Output {}: {}", data.target.as_pretty(), data.output);
+}
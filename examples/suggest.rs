@@ -0,0 +1,18 @@
+// Instances with suggestions enabled accept better translations through `Client::suggest()` or `Translation::suggest()`.
+
+use libretranslate::{Client, Language};
+
+#[tokio::main]
+async fn main() {
+    let client = Client::new("http://localhost:5000/")
+        .from_lang(Language::English)
+        .to_lang(Language::French);
+
+    let data = client.translate("Good morning!").await.unwrap();
+    println!("Output: {}", data.output);
+
+    match data.suggest(&client, "Bonjour !").await {
+        Ok(()) => println!("Suggestion sent"),
+        Err(error) => println!("Error: {}", error),
+    };
+}
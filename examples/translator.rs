@@ -0,0 +1,28 @@
+// Code that depends on the `Translator` trait works with a `Client`, `Pseudo` or any other translator.
+
+use libretranslate::{Client, Language, Pseudo, TranslationBuilder, Translator};
+
+async fn greet(translator: &dyn Translator) -> String {
+    let data = TranslationBuilder::new()
+        .translator(translator)
+        .from_lang(Language::English)
+        .to_lang(Language::French)
+        .text("Hello world!")
+        .translate()
+        .await
+        .unwrap();
+
+    data.output
+}
+
+#[tokio::main]
+async fn main() {
+    let translators: Vec<Box<dyn Translator>> = vec![
+        Box::new(Pseudo::new()),
+        Box::new(Client::new("https://libretranslate.de/")),
+    ];
+
+    for translator in &translators {
+        println!("{}", greet(translator.as_ref()).await);
+    }
+}
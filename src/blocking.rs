@@ -0,0 +1,109 @@
+//! Translate without an async runtime.
+//!
+//! Requests are sent with [`ureq`](ureq) and block the calling thread, which makes this module usable from
+//! `build.rs` scripts or any synchronous code. It is enabled by the `blocking` feature.
+//!
+//! ```rust,no_run
+//! use libretranslate::blocking;
+//! use libretranslate::Language;
+//!
+//! fn main() {
+//!     let data = blocking::translate(Language::English, Language::French, "Hello world!", None).unwrap();
+//!
+//!     println!("Output: \"{}\"", data.output);
+//! }
+//! ```
+
+use crate::{
+    Client, Detection, Language, Query, TranslateError, Translation, TranslationBuilder,
+    DEFAULT_URL,
+};
+use futures_executor::block_on;
+
+/// Translate text between two [`Language`](Language), see [`translate`](crate::translate).
+pub fn translate<T: AsRef<str>>(
+    source: Language,
+    target: Language,
+    input: T,
+    key: Option<T>,
+) -> Result<Translation, TranslateError> {
+    let key: Option<String> = key.map(|data| data.as_ref().to_string());
+
+    translate_url(source, target, input.as_ref(), DEFAULT_URL, key)
+}
+
+/// Translate using a custom URL, see [`translate_url`](crate::translate_url).
+pub fn translate_url<T: AsRef<str>>(
+    source: Language,
+    target: Language,
+    input: T,
+    url: T,
+    key: Option<String>,
+) -> Result<Translation, TranslateError> {
+    let client = client(url.as_ref(), key);
+
+    block_on(client.translate_text(source, target, input.as_ref()))
+}
+
+/// Detect the language of some text, see [`detect`](crate::detect).
+pub fn detect<T: AsRef<str>>(input: T, key: Option<T>) -> Result<Vec<Detection>, TranslateError> {
+    let key: Option<String> = key.map(|data| data.as_ref().to_string());
+
+    detect_url(input.as_ref(), DEFAULT_URL, key)
+}
+
+/// Detect the language of some text using a custom URL, see [`detect_url`](crate::detect_url).
+pub fn detect_url<T: AsRef<str>>(
+    input: T,
+    url: T,
+    key: Option<String>,
+) -> Result<Vec<Detection>, TranslateError> {
+    let client = client(url.as_ref(), key);
+
+    block_on(client.detect(input))
+}
+
+fn client(url: &str, key: Option<String>) -> Client {
+    let client = Client::new(url).blocking();
+
+    match key {
+        Some(key) => client.key(key),
+        None => client,
+    }
+}
+
+impl Query<'_> {
+    /// Like [`translate`](Query::translate), blocking the calling thread instead.
+    ///
+    /// A [`Client`](Client) set on the query keeps its options and cassette, but its requests are sent with
+    /// [`ureq`](ureq).
+    /// A [`Translator`](crate::Translator) set on it is run as is on the calling thread.
+    pub fn translate_blocking(self) -> Result<String, TranslateError> {
+        match self.translator {
+            Some(translator) => block_on(self.translate_with(translator)),
+            None => {
+                let client = self.prepared_client().blocking();
+
+                block_on(self.translate_with(&client))
+            }
+        }
+    }
+}
+
+impl TranslationBuilder<'_> {
+    /// Like [`translate`](TranslationBuilder::translate), blocking the calling thread instead.
+    ///
+    /// A [`Client`](Client) set on the builder keeps its options and cassette, but its requests are sent with
+    /// [`ureq`](ureq).
+    /// A [`Translator`](crate::Translator) set on it is run as is on the calling thread.
+    pub fn translate_blocking(self) -> Result<Translation, TranslateError> {
+        match self.translator {
+            Some(translator) => block_on(self.translate_with(translator)),
+            None => {
+                let client = self.prepared_client().blocking();
+
+                block_on(self.translate_with(&client))
+            }
+        }
+    }
+}
@@ -0,0 +1,434 @@
+/// A piece of a split text.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Segment<'a> {
+    /// Text to translate, shorter than the limit it was split with unless it's an HTML element longer than it.
+    Text(&'a str),
+    /// Whitespace between two pieces, kept as is in the output.
+    Separator(&'a str),
+}
+
+/// The boundaries text is split at, from the most to the least preferable.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Boundary {
+    Paragraph,
+    Sentence,
+    Word,
+}
+
+const BOUNDARIES: [Boundary; 3] = [Boundary::Paragraph, Boundary::Sentence, Boundary::Word];
+
+/// Characters that end a sentence when followed by whitespace.
+const SENTENCE_ENDS: [char; 7] = ['.', '!', '?', '…', '。', '！', '？'];
+
+/// Split `text` into segments of less than `limit` characters each.
+///
+/// Paragraph boundaries are preferred over sentence boundaries, which are preferred over word boundaries.
+/// Words longer than the limit are cut. With `markup`, `text` is HTML and is only split where no element is open,
+/// so each segment is valid markup on its own.
+/// Concatenating the segments gives back `text`.
+pub(crate) fn split(text: &str, limit: usize, markup: bool) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    split_at(text, limit.max(2), markup, &BOUNDARIES, &mut segments);
+    segments
+}
+
+fn split_at<'a>(
+    text: &'a str,
+    limit: usize,
+    markup: bool,
+    boundaries: &[Boundary],
+    out: &mut Vec<Segment<'a>>,
+) {
+    if text.chars().count() < limit {
+        out.push(Segment::Text(text));
+        return;
+    }
+
+    let (boundary, rest) = match boundaries.split_first() {
+        Some(split) => split,
+        None => return cut(text, limit, markup, out),
+    };
+
+    let ranges = pieces(text, *boundary, markup);
+
+    // Nothing to split at on this level, try the next one.
+    if ranges == [(0, text.len())] {
+        return split_at(text, limit, markup, rest, out);
+    }
+
+    // Greedily merge consecutive pieces (and what separates them) as long as they fit in the limit.
+    let mut pos = 0;
+    let mut run: Option<(usize, usize)> = None;
+
+    for (start, end) in ranges {
+        if let Some((run_start, _)) = run {
+            if text[run_start..end].chars().count() < limit {
+                run = Some((run_start, end));
+                continue;
+            }
+        }
+
+        if let Some((run_start, run_end)) = run.take() {
+            separator(&text[pos..run_start], out);
+            out.push(Segment::Text(&text[run_start..run_end]));
+            pos = run_end;
+        }
+
+        if text[start..end].chars().count() < limit {
+            run = Some((start, end));
+        } else {
+            separator(&text[pos..start], out);
+            split_at(&text[start..end], limit, markup, rest, out);
+            pos = end;
+        }
+    }
+
+    if let Some((run_start, run_end)) = run {
+        separator(&text[pos..run_start], out);
+        out.push(Segment::Text(&text[run_start..run_end]));
+        pos = run_end;
+    }
+
+    separator(&text[pos..], out);
+}
+
+/// Return the byte ranges of the pieces of `text` between `boundary`s, outside of elements with `markup`.
+fn pieces(text: &str, boundary: Boundary, markup: bool) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    let mut nesting = Nesting::default();
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        if markup {
+            nesting.step(text, i, c);
+        }
+
+        if !c.is_whitespace() || !nesting.top_level() {
+            continue;
+        }
+
+        let mut end = i + c.len_utf8();
+
+        while let Some(&(j, c)) = chars.peek() {
+            if !c.is_whitespace() {
+                break;
+            }
+
+            end = j + c.len_utf8();
+            chars.next();
+        }
+
+        let is_boundary = i == 0
+            || end == text.len()
+            || match boundary {
+                Boundary::Paragraph => text[i..end].matches('\n').count() >= 2,
+                Boundary::Sentence => text[..i].ends_with(SENTENCE_ENDS),
+                Boundary::Word => true,
+            };
+
+        if is_boundary {
+            if start < i {
+                ranges.push((start, i));
+            }
+
+            start = end;
+        }
+    }
+
+    if start < text.len() {
+        ranges.push((start, text.len()));
+    }
+
+    ranges
+}
+
+/// Cut `text` every `limit - 1` characters, for words that are longer than the limit.
+///
+/// With `markup`, a cut is only made where no element is open, as late as possible. An element longer than the
+/// limit is left whole.
+fn cut<'a>(text: &'a str, limit: usize, markup: bool, out: &mut Vec<Segment<'a>>) {
+    let mut start = 0;
+    let mut count = 0;
+    // The last position the text can be cut at, with the number of characters before it.
+    let mut last = None;
+    let mut nesting = Nesting::default();
+
+    for (i, c) in text.char_indices() {
+        if nesting.top_level() && i > start {
+            last = Some((i, count));
+        }
+
+        if count >= limit - 1 {
+            if let Some((at, before)) = last.take() {
+                out.push(Segment::Text(&text[start..at]));
+                start = at;
+                count -= before;
+
+                if nesting.top_level() && i > start {
+                    last = Some((i, count));
+                }
+            }
+        }
+
+        if markup {
+            nesting.step(text, i, c);
+        }
+
+        count += 1;
+    }
+
+    out.push(Segment::Text(&text[start..]));
+}
+
+/// How deep a position of an HTML text is in its elements.
+#[derive(Debug, Default)]
+struct Nesting {
+    /// Where the tag being read starts.
+    tag: Option<usize>,
+    depth: usize,
+}
+
+impl Nesting {
+    /// Move past the character `c` at byte `i` of `text`.
+    fn step(&mut self, text: &str, i: usize, c: char) {
+        match c {
+            '<' => self.tag = Some(i),
+            '>' => {
+                if let Some(start) = self.tag.take() {
+                    self.depth = match tag(&text[start..=i]) {
+                        Tag::Open(_) => self.depth + 1,
+                        Tag::Close(_) => self.depth.saturating_sub(1),
+                        Tag::Empty => self.depth,
+                    };
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether the text can be split here, outside of any tag and element.
+    fn top_level(&self) -> bool {
+        self.tag.is_none() && self.depth == 0
+    }
+}
+
+/// Elements that never have content nor a closing tag.
+const VOID_ELEMENTS: [&str; 14] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// What an HTML tag does to the elements around it.
+#[derive(Debug, PartialEq)]
+pub(crate) enum Tag {
+    /// Opens the element with this name, in lowercase.
+    Open(String),
+    /// Closes the element with this name, in lowercase.
+    Close(String),
+    /// Void elements, self-closing tags, comments and doctypes.
+    Empty,
+}
+
+/// Read the tag `text` (ex. `<a href="x">`), from its `<` to its `>`.
+pub(crate) fn tag(text: &str) -> Tag {
+    let inner = text.trim_start_matches('<').trim_end_matches('>');
+
+    if inner.starts_with(['!', '?']) || inner.ends_with('/') {
+        return Tag::Empty;
+    }
+
+    let (closing, inner) = match inner.strip_prefix('/') {
+        Some(inner) => (true, inner),
+        None => (false, inner),
+    };
+
+    let end = inner
+        .find(|c: char| c.is_whitespace() || c == '/')
+        .unwrap_or(inner.len());
+    let name = inner[..end].to_ascii_lowercase();
+
+    if closing {
+        Tag::Close(name)
+    } else if VOID_ELEMENTS.contains(&name.as_str()) {
+        Tag::Empty
+    } else {
+        Tag::Open(name)
+    }
+}
+
+fn separator<'a>(text: &'a str, out: &mut Vec<Segment<'a>>) {
+    if !text.is_empty() {
+        out.push(Segment::Separator(text));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts<'a>(segments: &[Segment<'a>]) -> Vec<&'a str> {
+        segments
+            .iter()
+            .filter_map(|segment| match segment {
+                Segment::Text(text) => Some(*text),
+                Segment::Separator(_) => None,
+            })
+            .collect()
+    }
+
+    fn join(segments: &[Segment<'_>]) -> String {
+        segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Text(text) | Segment::Separator(text) => *text,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn short_text() {
+        assert_eq!(
+            split("Hello world.", 100, false),
+            [Segment::Text("Hello world.")]
+        );
+    }
+
+    #[test]
+    fn paragraphs_first() {
+        let text = "First sentence. Second one.\n\nA new paragraph.";
+
+        assert_eq!(
+            split(text, 30, false),
+            [
+                Segment::Text("First sentence. Second one."),
+                Segment::Separator("\n\n"),
+                Segment::Text("A new paragraph."),
+            ]
+        );
+    }
+
+    #[test]
+    fn sentences_then_words() {
+        let text = "The first sentence is here. The second one follows.";
+
+        assert_eq!(
+            texts(&split(text, 30, false)),
+            ["The first sentence is here.", "The second one follows."]
+        );
+        assert_eq!(
+            texts(&split("one two three four", 10, false)),
+            ["one two", "three", "four"]
+        );
+    }
+
+    #[test]
+    fn long_words_are_cut() {
+        assert_eq!(
+            texts(&split("abcdefghij", 5, false)),
+            ["abcd", "efgh", "ij"]
+        );
+    }
+
+    #[test]
+    fn markup() {
+        assert_eq!(
+            texts(&split("<p>Hello there</p> <p>General Kenobi</p>", 25, true)),
+            ["<p>Hello there</p>", "<p>General Kenobi</p>"]
+        );
+        assert_eq!(
+            texts(&split("Hello <b>big</b> world", 10, true)),
+            ["Hello", "<b>big</b>", "world"]
+        );
+        assert_eq!(
+            texts(&split("abcdefgh<br>ij", 5, true)),
+            ["abcd", "efgh", "<br>", "ij"]
+        );
+        // An element longer than the limit can't be split.
+        let text = r#"<a href="x" class="y">Hello there friend</a>"#;
+        assert_eq!(texts(&split(text, 30, true)), [text]);
+        assert_eq!(
+            texts(&split("<b>abcdefgh</b>", 6, true)),
+            ["<b>abcdefgh</b>"]
+        );
+    }
+
+    #[test]
+    fn tags() {
+        assert_eq!(tag(r#"<a href="x">"#), Tag::Open(String::from("a")));
+        assert_eq!(tag("<DIV>"), Tag::Open(String::from("div")));
+        assert_eq!(tag("</A>"), Tag::Close(String::from("a")));
+        assert_eq!(tag("<br>"), Tag::Empty);
+        assert_eq!(tag(r#"<img src="a.png"/>"#), Tag::Empty);
+        assert_eq!(tag("<span/>"), Tag::Empty);
+        assert_eq!(tag("<!-- note -->"), Tag::Empty);
+    }
+
+    #[test]
+    fn invariants() {
+        let mut rng = fastrand::Rng::with_seed(7);
+        let words = [
+            "a",
+            "word",
+            "longerword",
+            "ünïcödé",
+            "。",
+            "end.",
+            "why?",
+            "\n",
+            "\n\n",
+            " ",
+            "  ",
+        ];
+        let tags = ["<b>", "</b>", r#"<a href="x" class="y">"#, "</a>", "<br>"];
+
+        for _ in 0..1000 {
+            let markup = rng.bool();
+
+            let text: String = (0..rng.usize(0..80))
+                .map(|_| {
+                    if markup && rng.u8(..4) == 0 {
+                        tags[rng.usize(..tags.len())].to_string()
+                    } else {
+                        format!("{} ", words[rng.usize(..words.len())])
+                    }
+                })
+                .collect();
+            let limit = rng.usize(1..40);
+
+            let segments = split(&text, limit, markup);
+
+            assert_eq!(join(&segments), text);
+
+            let mut nesting = Nesting::default();
+            let mut chars = text.char_indices().peekable();
+            let mut offset = 0;
+
+            for segment in segments {
+                let piece = match segment {
+                    Segment::Text(text) | Segment::Separator(text) => text,
+                };
+
+                if let Segment::Text(piece) = segment {
+                    assert!(
+                        piece.chars().count() < limit.max(2) || (markup && piece.contains('<')),
+                        "{:?} with {}",
+                        piece,
+                        limit
+                    );
+                }
+
+                // Segments never start inside a tag or an element.
+                assert!(nesting.top_level(), "{:?} starts inside an element", piece);
+
+                offset += piece.len();
+
+                while let Some((i, c)) = chars.next_if(|(i, _)| *i < offset) {
+                    if markup {
+                        nesting.step(&text, i, c);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,1446 @@
+use crate::chunk::{self, Segment};
+use crate::limit::Limiter;
+use crate::models::{
+    self, BatchTranslateResponse, DetectRequest, SettingsResponse, SuggestRequest, SuggestResponse,
+    TranslateFileRequest, TranslateFileResponse, TranslateRequest, TranslateResponse,
+};
+use crate::pseudo;
+#[cfg(feature = "reqwest")]
+use crate::transport::ReqwestTransport;
+#[cfg(feature = "surf")]
+use crate::transport::SurfTransport;
+#[cfg(feature = "ureq")]
+use crate::transport::UreqTransport;
+use crate::transport::{self, Request, Response, Transport};
+#[cfg(feature = "cassette")]
+use crate::transport::{RecordTransport, ReplayTransport};
+use crate::{
+    Format, Language, LanguageError, Pseudo, RateLimit, RetryPolicy, TranslateError, Translation,
+    TranslationBuilder, Translator, DEFAULT_URL,
+};
+use futures_timer::Delay;
+use futures_util::future::{self, Either};
+use futures_util::stream::{self, StreamExt};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::future::Future;
+use std::path::Path;
+use std::pin::pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, PoisonError};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// The number of characters LibreTranslate accepts in a single request by default.
+const DEFAULT_CHAR_LIMIT: usize = 5000;
+
+/// How many pieces of a chunked text are translated at the same time.
+const CHUNK_CONCURRENCY: usize = 4;
+
+/// How long a failed instance is skipped by a client with fallbacks.
+const INSTANCE_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// How long an instance that couldn't send its settings isn't asked for them again.
+const SETTINGS_RETRY: Duration = Duration::from_secs(10);
+
+/// A reusable handle to a single LibreTranslate instance.
+///
+/// The client owns the instance URL, an optional API key, the default languages and a shared HTTP client,
+/// so every request made through it reuses the same connection pool. Cloning a `Client` is cheap.
+///
+/// Requests are sent with [`surf`](https://docs.rs/surf) by default. With `default-features = false`, the
+/// `reqwest` feature (with `reqwest-rustls` or `reqwest-native-tls` for HTTPS) or the blocking `ureq`
+/// feature can be used instead.
+#[derive(Debug, Clone)]
+pub struct Client {
+    url: String,
+    key: Option<String>,
+    pub(crate) source: Language,
+    pub(crate) target: Language,
+    min_confidence: Option<f32>,
+    char_limit: Option<usize>,
+    chunked: bool,
+    format: Format,
+    alternatives: usize,
+    retry: Option<RetryPolicy>,
+    limiter: Option<Arc<Limiter>>,
+    fallbacks: Vec<Instance>,
+    round_robin: Option<Arc<AtomicUsize>>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    /// The HTTP client set with `http_client` or the likes, instead of the one the client builds itself.
+    http: Option<Arc<dyn Transport>>,
+    transport: Arc<dyn Transport>,
+    backend: Option<Arc<dyn Translator>>,
+}
+
+impl Client {
+    /// Create a client for the instance at `url` (ex. "https://libretranslate.com/").
+    pub fn new<T: AsRef<str>>(url: T) -> Self {
+        Self {
+            url: url.as_ref().to_string(),
+            key: None,
+            source: Language::Detect,
+            target: Language::default(),
+            min_confidence: None,
+            char_limit: None,
+            chunked: false,
+            format: Format::default(),
+            alternatives: 0,
+            retry: None,
+            limiter: None,
+            fallbacks: Vec::new(),
+            round_robin: None,
+            timeout: None,
+            connect_timeout: None,
+            http: None,
+            transport: transport::default(None),
+            backend: None,
+        }
+    }
+
+    /// Create a client that pseudo-localizes texts itself instead of sending them to an instance, see [`Pseudo`](Pseudo).
+    ///
+    /// Translations, batches, detections and languages work offline like with any other client, and come from
+    /// "pseudo://localization/". Every text is detected as English. Files can't be
+    /// translated, and [`suggest`](Client::suggest) and [`info`](Client::info) fail with
+    /// [`TranslateError::TransportError`](TranslateError::TransportError) since there is no instance to ask.
+    pub fn pseudo(pseudo: Pseudo) -> Self {
+        Self {
+            backend: Some(Arc::new(pseudo)),
+            ..Self::new(pseudo::URL)
+        }
+    }
+
+    /// Set the API key sent with every request.
+    pub fn key<T: AsRef<str>>(mut self, key: T) -> Self {
+        self.key = Some(key.as_ref().to_string());
+        self
+    }
+
+    /// Set the default source language. Defaults to [`Language::Detect`](Language::Detect).
+    pub fn from_lang(mut self, lang: Language) -> Self {
+        self.source = lang;
+        self
+    }
+
+    /// Set the default target language. Defaults to [`Language::default()`](Language::default).
+    pub fn to_lang(mut self, lang: Language) -> Self {
+        self.target = lang;
+        self
+    }
+
+    /// Make [`detect`](Client::detect) fail with [`TranslateError::DetectError`](TranslateError::DetectError)
+    /// when the best candidate's confidence is below `confidence` (from 0 to 100).
+    pub fn min_confidence(mut self, confidence: f32) -> Self {
+        self.min_confidence = Some(confidence);
+        self
+    }
+
+    /// Set the number of characters the instance accepts in a single request.
+    ///
+    /// Defaults to the limit advertised by the instance (see [`Client::info`](Client::info)), or 5000 if it doesn't.
+    pub fn char_limit(mut self, limit: usize) -> Self {
+        self.char_limit = Some(limit);
+        self
+    }
+
+    /// Split texts longer than the [`char_limit`](Client::char_limit) at paragraph, sentence or word boundaries
+    /// and translate the pieces separately instead of failing with [`TranslateError::LengthError`](TranslateError::LengthError).
+    ///
+    /// With [`Format::Html`](Format::Html), texts are only split where no element is open, so every piece is valid
+    /// markup. An element longer than the limit still fails with [`TranslateError::LengthError`](TranslateError::LengthError).
+    pub fn chunked(mut self, chunked: bool) -> Self {
+        self.chunked = chunked;
+        self
+    }
+
+    /// Set the format of the texts sent for translation. Defaults to [`Format::Text`](Format::Text).
+    pub fn format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Ask the instance for up to `count` alternative translations, returned in
+    /// [`Translation::alternatives`](Translation::alternatives). Chunked translations have none.
+    pub fn alternatives(mut self, count: usize) -> Self {
+        self.alternatives = count;
+        self
+    }
+
+    /// Retry requests that fail with a transient error according to `policy`. Requests aren't retried by default.
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+
+    /// Pace requests to stay under `limit` on each instance, the client's own and every [`fallback`](Client::fallback).
+    /// The limit is shared with the clones of this client, so concurrent translations queue instead of being refused
+    /// with [`TranslateError::RateLimitError`](TranslateError::RateLimitError).
+    pub fn rate_limit(mut self, limit: RateLimit) -> Self {
+        self.limiter = Some(Arc::new(Limiter::new(limit)));
+        self
+    }
+
+    /// Add an instance to fall back to when a request fails with a transient error (see
+    /// [`RetryPolicy::is_transient`](RetryPolicy::is_transient)). Instances are tried in the order they were added,
+    /// after the client's own URL. [`Translation::url`](Translation::url) tells which one served a translation.
+    ///
+    /// An instance that failed is skipped for 30 seconds, unless every instance failed.
+    pub fn fallback(mut self, instance: Instance) -> Self {
+        self.fallbacks.push(instance);
+        self
+    }
+
+    /// Spread requests over the client's URL and its [`fallback`](Client::fallback)s in turn, instead of
+    /// always starting with the client's URL. The turn is shared with the clones of this client.
+    pub fn round_robin(mut self, round_robin: bool) -> Self {
+        self.round_robin = round_robin.then(Arc::default);
+        self
+    }
+
+    /// Fail with [`TranslateError::Timeout`](TranslateError::Timeout) when the instance takes longer than `timeout`
+    /// to answer a request. Each retry gets its own `timeout`.
+    ///
+    /// The settings a client asks an instance for before its first translation count as a request: when they
+    /// time out, so does the translation, without waiting for the instance a second time.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Fail with [`TranslateError::Timeout`](TranslateError::Timeout) when connecting to the instance takes longer
+    /// than `timeout`.
+    ///
+    /// It applies to the HTTP client the client builds itself. One set with [`http_client`](Client::http_client)
+    /// or the likes keeps its own settings, and replayed cassettes and [`pseudo`](Client::pseudo) clients don't
+    /// connect to anything.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+
+        if self.http.is_none() {
+            self.transport = self.transport.with_http(transport::default(Some(timeout)));
+        }
+
+        self
+    }
+
+    /// Use an already configured [`surf::Client`](surf::Client) for the requests.
+    #[cfg(feature = "surf")]
+    pub fn http_client(self, http: surf::Client) -> Self {
+        self.with_http(Arc::new(SurfTransport::from(http)))
+    }
+
+    /// Use an already configured [`reqwest::Client`](reqwest::Client) for the requests.
+    #[cfg(feature = "reqwest")]
+    pub fn reqwest_client(self, http: reqwest::Client) -> Self {
+        self.with_http(Arc::new(ReqwestTransport::from(http)))
+    }
+
+    /// Use an already configured [`ureq::Agent`](ureq::Agent) for the requests. They then block the calling thread.
+    #[cfg(feature = "ureq")]
+    pub fn ureq_agent(self, agent: ureq::Agent) -> Self {
+        self.with_http(Arc::new(UreqTransport::from(agent)))
+    }
+
+    fn with_http(mut self, http: Arc<dyn Transport>) -> Self {
+        self.http = Some(http.clone());
+        self.transport = self.transport.with_http(http);
+        self
+    }
+
+    /// Record every request and the response it got to a cassette file at `path`, so they can be
+    /// [`replay`](Client::replay)ed later without a network. API keys aren't written down.
+    ///
+    /// Requests are still sent with the client's HTTP client, even one set after this. The file is overwritten.
+    #[cfg(feature = "cassette")]
+    pub fn record<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.transport = Arc::new(RecordTransport::new(self.transport, path));
+        self
+    }
+
+    /// Answer requests with the responses of a cassette file written by [`record`](Client::record),
+    /// instead of sending them.
+    ///
+    /// Requests are matched by method, path and body, whatever the instance URL. The ones that were recorded
+    /// several times get their responses in order. A request that wasn't recorded fails with
+    /// [`TranslateError::TransportError`](TranslateError::TransportError).
+    #[cfg(feature = "cassette")]
+    pub fn replay<P: AsRef<Path>>(mut self, path: P) -> Result<Self, TranslateError> {
+        self.transport = Arc::new(ReplayTransport::open(path)?);
+        Ok(self)
+    }
+
+    /// Send requests with [`ureq`](ureq), unless they already block, so they can be made without a runtime.
+    /// A cassette still records them, and the [`connect_timeout`](Client::connect_timeout) still applies.
+    #[cfg(feature = "blocking")]
+    pub(crate) fn blocking(mut self) -> Self {
+        if !self.transport.is_blocking() {
+            let http = Arc::new(UreqTransport::new(self.connect_timeout));
+            self.transport = self.transport.with_http(http);
+        }
+
+        self
+    }
+
+    /// The base URL of the instance this client talks to.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Translate text between the client's default languages.
+    pub async fn translate<T: AsRef<str>>(&self, input: T) -> Result<Translation, TranslateError> {
+        self.translate_text(self.source, self.target, input.as_ref())
+            .await
+    }
+
+    /// Start a [`TranslationBuilder`](TranslationBuilder) bound to this client and its default languages.
+    pub fn text<T: AsRef<str>>(&self, input: T) -> TranslationBuilder<'_> {
+        TranslationBuilder::new()
+            .client(self)
+            .from_lang(self.source)
+            .to_lang(self.target)
+            .text(input)
+    }
+
+    /// Detect the language of some text.
+    ///
+    /// The candidates are ranked from the most to the least confident.
+    pub async fn detect<T: AsRef<str>>(&self, input: T) -> Result<Vec<Detection>, TranslateError> {
+        let input = input.as_ref();
+
+        let mut detections: Vec<Detection> = match &self.backend {
+            Some(backend) => backend.detect(input).await?,
+            None => {
+                self.failover(|client| async move {
+                    let data = DetectRequest {
+                        q: input,
+                        api_key: client.key.as_deref(),
+                    };
+
+                    client.post("detect", &data, input.chars().count()).await
+                })
+                .await?
+            }
+        };
+
+        detections.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+
+        match (detections.first(), self.min_confidence) {
+            (None, _) => return Err(TranslateError::DetectError),
+            (Some(best), Some(min_confidence)) if best.confidence < min_confidence => {
+                return Err(TranslateError::DetectError)
+            }
+            _ => (),
+        };
+
+        Ok(detections)
+    }
+
+    /// List the languages served by the instance and the targets each of them can be translated to.
+    ///
+    /// The list is fetched once per instance and cached for the lifetime of the process. Once it is known,
+    /// translations to or from this instance are checked against it and fail early with
+    /// [`TranslateError::PairError`](TranslateError::PairError) instead of making a request.
+    pub async fn languages(&self) -> Result<Vec<LanguageInfo>, TranslateError> {
+        let cached = cache()
+            .get(self.cache_key())
+            .and_then(|cached| cached.languages.clone());
+
+        match cached {
+            Some(languages) => Ok(languages),
+            None => self.refresh_languages().await,
+        }
+    }
+
+    /// Fetch the languages served by the instance again, replacing the cached list.
+    pub async fn refresh_languages(&self) -> Result<Vec<LanguageInfo>, TranslateError> {
+        let infos: Vec<LanguageInfo> = match &self.backend {
+            Some(backend) => backend.languages().await?,
+            None => self.get("languages").await?,
+        };
+
+        cache()
+            .entry(self.cache_key().to_string())
+            .or_default()
+            .languages = Some(infos.clone());
+
+        Ok(infos)
+    }
+
+    /// Fetch what the instance advertises about itself at `/frontend/settings`.
+    ///
+    /// Like [`languages`](Client::languages), it is fetched once per instance and cached. The client uses it
+    /// to apply the instance's character limit, to fail early with [`TranslateError::KeyError`](TranslateError::KeyError)
+    /// when a key is required but none was set, and to reject files the instance can't translate.
+    pub async fn info(&self) -> Result<InstanceInfo, TranslateError> {
+        let cached = cache()
+            .get(self.cache_key())
+            .and_then(|cached| cached.info.clone());
+
+        match cached {
+            Some(Some(info)) => Ok(info),
+            _ => self.refresh_info().await,
+        }
+    }
+
+    /// Fetch the instance's settings again, replacing the cached ones.
+    pub async fn refresh_info(&self) -> Result<InstanceInfo, TranslateError> {
+        self.check_backend()?;
+
+        let settings: SettingsResponse = self.get("frontend/settings").await?;
+
+        Ok(self.cache_info(settings))
+    }
+
+    fn cache_info(&self, settings: SettingsResponse) -> InstanceInfo {
+        let info = settings.into_info(DEFAULT_CHAR_LIMIT);
+
+        cache()
+            .entry(self.cache_key().to_string())
+            .or_default()
+            .info = Some(Some(info.clone()));
+
+        info
+    }
+
+    /// Translate many texts between the client's default languages.
+    ///
+    /// The texts are packed into as few requests as the instance's character limit allows, and the results
+    /// come back in the same order. Each text gets its own result, so a failed request doesn't lose the others.
+    /// A text longer than the limit fails with [`TranslateError::LengthError`](TranslateError::LengthError), or is
+    /// split and translated on its own when the client is [`chunked`](Client::chunked).
+    pub async fn translate_batch<I, T>(&self, inputs: I) -> Vec<Result<Translation, TranslateError>>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let inputs: Vec<T> = inputs.into_iter().collect();
+        let inputs: Vec<&str> = inputs.iter().map(AsRef::as_ref).collect();
+
+        self.translate_batch_text(self.source, self.target, &inputs)
+            .await
+    }
+
+    /// Submit a better translation of `original` to an instance with suggestions enabled.
+    pub async fn suggest<T: AsRef<str>>(
+        &self,
+        original: T,
+        corrected: T,
+        source: Language,
+        target: Language,
+    ) -> Result<(), TranslateError> {
+        self.check_backend()?;
+
+        // The instance needs to know which language the original text is in.
+        if source == Language::Detect {
+            return Err(TranslateError::DetectError);
+        }
+
+        let data = SuggestRequest {
+            q: original.as_ref(),
+            s: corrected.as_ref(),
+            source: source.as_code(),
+            target: target.as_code(),
+            api_key: self.key.as_deref(),
+        };
+
+        let res: SuggestResponse = self.post("suggest", &data, data.q.chars().count()).await?;
+
+        if res.success {
+            Ok(())
+        } else {
+            Err(TranslateError::decode(
+                "The instance didn't accept the suggestion",
+            ))
+        }
+    }
+
+    /// Translate a document (.txt, .odt, .docx, .pptx, .epub, .html, ...) and return the translated file.
+    ///
+    /// The file type is guessed by the instance from the file's extension.
+    pub async fn translate_file<P: AsRef<Path>>(
+        &self,
+        path: P,
+        source: Language,
+        target: Language,
+    ) -> Result<Vec<u8>, TranslateError> {
+        let path = path.as_ref();
+
+        let bytes = match std::fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(error) => return Err(TranslateError::file(error)),
+        };
+
+        let name = match path.file_name().and_then(OsStr::to_str) {
+            Some(name) => name,
+            None => {
+                return Err(TranslateError::file(format!(
+                    "Invalid file name: {}",
+                    path.display()
+                )))
+            }
+        };
+
+        self.translate_file_bytes(name, &bytes, source, target)
+            .await
+    }
+
+    /// Translate a document and write the translated file to `destination`.
+    pub async fn translate_file_to<P: AsRef<Path>, Q: AsRef<Path>>(
+        &self,
+        path: P,
+        destination: Q,
+        source: Language,
+        target: Language,
+    ) -> Result<(), TranslateError> {
+        let bytes = self.translate_file(path, source, target).await?;
+
+        match std::fs::write(destination, bytes) {
+            Ok(()) => Ok(()),
+            Err(error) => Err(TranslateError::file(error)),
+        }
+    }
+
+    /// Translate a document already in memory. `name` is the file's name, its extension tells the
+    /// instance the file type (ex. "contract.docx").
+    pub async fn translate_file_bytes(
+        &self,
+        name: &str,
+        bytes: &[u8],
+        source: Language,
+        target: Language,
+    ) -> Result<Vec<u8>, TranslateError> {
+        if self.backend.is_some() {
+            return Err(TranslateError::file(
+                "Files can't be translated without an instance",
+            ));
+        }
+
+        self.failover(|client| async move {
+            client.translate_document(name, bytes, source, target).await
+        })
+        .await
+    }
+
+    /// Translate a document on this client's instance only.
+    async fn translate_document(
+        &self,
+        name: &str,
+        bytes: &[u8],
+        source: Language,
+        target: Language,
+    ) -> Result<Vec<u8>, TranslateError> {
+        self.check_pair(source, target)?;
+        self.check_key().await?;
+
+        if let Some(info) = self.settings().await? {
+            if !info.supports_file(name) {
+                return Err(TranslateError::file(format!(
+                    "This instance can't translate {}",
+                    name
+                )));
+            }
+        }
+
+        let data = TranslateFileRequest {
+            source: source.as_code(),
+            target: target.as_code(),
+            api_key: self.key.as_deref(),
+        };
+
+        let (boundary, body) = multipart(&data.fields(), name, bytes);
+
+        let url = self.endpoint("translate_file");
+        let content_type = format!("multipart/form-data; boundary={}", boundary);
+
+        // The characters of a document are only known once the instance extracted them.
+        let res: TranslateFileResponse = self
+            .send(0, Request::post(&url, &content_type, &body))
+            .await?;
+
+        self.retrying(0, Request::get(&res.translated_file_url), |res| {
+            if !(200..300).contains(&res.status) {
+                return Err(TranslateError::from_response(
+                    res.status,
+                    res.retry_after.as_deref(),
+                    String::from("Unable to download the translated file"),
+                ));
+            }
+
+            Ok(res.body)
+        })
+        .await
+    }
+
+    pub(crate) async fn translate_text(
+        &self,
+        source: Language,
+        target: Language,
+        input: &str,
+    ) -> Result<Translation, TranslateError> {
+        // Nothing to send, answer as the instance would.
+        if input.is_empty() {
+            return Ok(Translation {
+                url: self.endpoint("translate"),
+                source,
+                target,
+                input: String::new(),
+                output: String::new(),
+                confidence: None,
+                alternatives: Vec::new(),
+            });
+        }
+
+        if let Some(backend) = &self.backend {
+            return backend.translate(source, target, input).await;
+        }
+
+        self.failover(|client| async move { client.translate_limited(source, target, input).await })
+            .await
+    }
+
+    /// Translate a text on this client's instance only, splitting it if the client is chunked.
+    async fn translate_limited(
+        &self,
+        source: Language,
+        target: Language,
+        input: &str,
+    ) -> Result<Translation, TranslateError> {
+        // Asked for before the pieces of a chunked text are sent at the same time.
+        self.check_key().await?;
+
+        let limit = self.limit().await?;
+
+        if input.chars().count() >= limit {
+            if self.chunked {
+                return self.translate_chunked(source, target, input, limit).await;
+            }
+
+            return Err(TranslateError::LengthError);
+        };
+
+        self.translate_single(source, target, input).await
+    }
+
+    /// Translate a text that fits in a single request.
+    async fn translate_single(
+        &self,
+        source: Language,
+        target: Language,
+        input: &str,
+    ) -> Result<Translation, TranslateError> {
+        self.check_pair(source, target)?;
+
+        let data = TranslateRequest {
+            q: input,
+            source: source.as_code(),
+            target: target.as_code(),
+            format: self.format.as_str(),
+            alternatives: self.requested_alternatives(),
+            api_key: self.key.as_deref(),
+        };
+
+        let res: TranslateResponse = self.post("translate", &data, input.chars().count()).await?;
+
+        Ok(self.translation(
+            source,
+            target,
+            input,
+            res.translated_text,
+            res.detected_language,
+            res.alternatives,
+        ))
+    }
+
+    /// Translate a text that is too long for a single request by splitting it into pieces.
+    async fn translate_chunked(
+        &self,
+        source: Language,
+        target: Language,
+        input: &str,
+        limit: usize,
+    ) -> Result<Translation, TranslateError> {
+        let markup = self.format == Format::Html;
+        let segments = chunk::split(input, limit, markup);
+
+        let texts: Vec<&str> = segments
+            .iter()
+            .filter_map(|segment| match segment {
+                Segment::Text(text) => Some(*text),
+                Segment::Separator(_) => None,
+            })
+            .collect();
+
+        // An HTML element longer than the limit is left whole, and can't be sent.
+        if markup && texts.iter().any(|text| text.chars().count() >= limit) {
+            return Err(TranslateError::LengthError);
+        }
+
+        let mut source = source;
+        let mut confidence = None;
+        let mut outputs = Vec::with_capacity(texts.len());
+        let mut rest = &texts[..];
+
+        // Detect the language on the first piece only, so every piece is translated from the same one.
+        if source == Language::Detect {
+            if let Some((first, others)) = texts.split_first() {
+                let data = self.translate_single(source, target, first).await?;
+
+                source = data.source;
+                confidence = data.confidence;
+                outputs.push(data.output);
+                rest = others;
+            }
+        }
+
+        // Collecting the futures first keeps the returned future `Send`.
+        let requests: Vec<_> = rest
+            .iter()
+            .map(|text| self.translate_single(source, target, text))
+            .collect();
+
+        let translations: Vec<Result<Translation, TranslateError>> = stream::iter(requests)
+            .buffered(CHUNK_CONCURRENCY)
+            .collect()
+            .await;
+
+        for translation in translations {
+            outputs.push(translation?.output);
+        }
+
+        let mut outputs = outputs.into_iter();
+        let mut output = String::with_capacity(input.len());
+
+        for segment in segments {
+            match segment {
+                Segment::Text(_) => output.push_str(&outputs.next().unwrap_or_default()),
+                Segment::Separator(separator) => output.push_str(separator),
+            }
+        }
+
+        Ok(Translation {
+            url: self.endpoint("translate"),
+            source,
+            target,
+            input: input.to_string(),
+            output,
+            confidence,
+            alternatives: Vec::new(),
+        })
+    }
+
+    pub(crate) async fn translate_batch_text(
+        &self,
+        source: Language,
+        target: Language,
+        inputs: &[&str],
+    ) -> Vec<Result<Translation, TranslateError>> {
+        if let Some(backend) = &self.backend {
+            return backend.translate_batch(source, target, inputs).await;
+        }
+
+        let limit = match self.limit().await {
+            Ok(limit) => limit,
+            Err(error) => return inputs.iter().map(|_| Err(error.clone())).collect(),
+        };
+
+        let mut results = Vec::with_capacity(inputs.len());
+
+        for batch in pack(inputs, limit) {
+            match batch {
+                Batch::Oversized(input) if self.chunked => results.push(
+                    self.failover(|client| async move {
+                        client.translate_limited(source, target, input).await
+                    })
+                    .await,
+                ),
+                Batch::Oversized(_) => results.push(Err(TranslateError::LengthError)),
+                Batch::Texts(texts) => match self
+                    .failover(|client| async move {
+                        client.translate_packed(source, target, texts).await
+                    })
+                    .await
+                {
+                    Ok(translations) => results.extend(translations),
+                    Err(error) => results.extend(texts.iter().map(|_| Err(error.clone()))),
+                },
+            }
+        }
+
+        results
+    }
+
+    /// Translate texts that fit in a single request.
+    async fn translate_packed(
+        &self,
+        source: Language,
+        target: Language,
+        inputs: &[&str],
+    ) -> Result<Vec<Result<Translation, TranslateError>>, TranslateError> {
+        self.check_pair(source, target)?;
+
+        let data = TranslateRequest {
+            q: inputs,
+            source: source.as_code(),
+            target: target.as_code(),
+            format: self.format.as_str(),
+            alternatives: self.requested_alternatives(),
+            api_key: self.key.as_deref(),
+        };
+
+        let chars = inputs.iter().map(|input| input.chars().count()).sum();
+
+        let res: BatchTranslateResponse = self.post("translate", &data, chars).await?;
+
+        if res.translated_text.len() != inputs.len() {
+            return Err(TranslateError::decode(format!(
+                "Expected {} translations, got {}",
+                inputs.len(),
+                res.translated_text.len()
+            )));
+        }
+
+        let mut detected = res.detected_language.into_iter();
+        let mut alternatives = res.alternatives.into_iter();
+
+        Ok(inputs
+            .iter()
+            .zip(res.translated_text)
+            .map(|(input, output)| {
+                Ok(self.translation(
+                    source,
+                    target,
+                    input,
+                    output,
+                    detected.next(),
+                    alternatives.next().unwrap_or_default(),
+                ))
+            })
+            .collect())
+    }
+
+    /// Build a [`Translation`](Translation) out of a response.
+    fn translation(
+        &self,
+        source: Language,
+        target: Language,
+        input: &str,
+        output: String,
+        detected: Option<Detection>,
+        alternatives: Vec<String>,
+    ) -> Translation {
+        // When the source is detected, the instance tells us which language it picked.
+        let (source, confidence) = match detected {
+            Some(detected) => (detected.language, Some(detected.confidence)),
+            None => (source, None),
+        };
+
+        Translation {
+            url: self.endpoint("translate"),
+            source,
+            target,
+            input: input.to_string(),
+            output,
+            confidence,
+            alternatives,
+        }
+    }
+
+    fn requested_alternatives(&self) -> Option<usize> {
+        match self.alternatives {
+            0 => None,
+            count => Some(count),
+        }
+    }
+
+    /// Check a language pair against the cached languages of the instance, if they were fetched.
+    fn check_pair(&self, source: Language, target: Language) -> Result<(), TranslateError> {
+        let cache = cache();
+
+        let languages = match cache
+            .get(self.cache_key())
+            .and_then(|cached| cached.languages.as_ref())
+        {
+            Some(languages) => languages,
+            None => return Ok(()),
+        };
+
+        let served =
+            |language: Language| languages.iter().any(|info| info.code == language.as_code());
+
+        let supported = match source {
+            Language::Detect => served(target),
+            source => match languages.iter().find(|info| info.code == source.as_code()) {
+                Some(info) if info.targets.is_empty() => served(target),
+                Some(info) => info.supports(target),
+                None => false,
+            },
+        };
+
+        if supported {
+            Ok(())
+        } else {
+            Err(TranslateError::PairError(source, target))
+        }
+    }
+
+    /// Fail with [`TranslateError::TransportError`](TranslateError::TransportError) for what only an instance can
+    /// answer, when the client translates with a backend instead.
+    fn check_backend(&self) -> Result<(), TranslateError> {
+        match self.backend {
+            Some(_) => Err(TranslateError::transport(
+                "This client translates without an instance",
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Fail early if the instance requires a key and none was set.
+    async fn check_key(&self) -> Result<(), TranslateError> {
+        if self.key.is_none() {
+            if let Some(info) = self.settings().await? {
+                if info.key_required {
+                    return Err(TranslateError::KeyError(String::from(
+                        "An API key is required by this instance",
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The number of characters that can be sent in a single request.
+    async fn limit(&self) -> Result<usize, TranslateError> {
+        if let Some(limit) = self.char_limit {
+            return Ok(limit);
+        }
+
+        Ok(match self.settings().await? {
+            Some(info) => info.char_limit.unwrap_or(usize::MAX),
+            None => DEFAULT_CHAR_LIMIT,
+        })
+    }
+
+    /// The instance's settings, fetched once they could be. `None` if the instance doesn't expose them or
+    /// couldn't answer lately.
+    ///
+    /// Fails with [`TranslateError::Timeout`](TranslateError::Timeout) when the instance didn't answer in time, rather
+    /// than have the request that needed the settings wait as long again.
+    async fn settings(&self) -> Result<Option<InstanceInfo>, TranslateError> {
+        if let Some(cached) = cache().get(self.cache_key()) {
+            if let Some(info) = &cached.info {
+                return Ok(info.clone());
+            }
+
+            if cached
+                .info_retry
+                .is_some_and(|retry| retry > Instant::now())
+            {
+                return Ok(None);
+            }
+        }
+
+        // Sent once without retries, so an instance without settings doesn't delay every first request.
+        if let Some(limiter) = &self.limiter {
+            limiter.acquire(self.cache_key(), 0).await;
+        }
+
+        let url = self.endpoint("frontend/settings");
+
+        match self.request(Request::get(&url)).await.and_then(decode) {
+            Ok(settings) => Ok(Some(self.cache_info(settings))),
+            // Older instances don't have the endpoint.
+            Err(TranslateError::ServerError(404, _) | TranslateError::DecodeError(_)) => {
+                cache()
+                    .entry(self.cache_key().to_string())
+                    .or_default()
+                    .info = Some(None);
+                Ok(None)
+            }
+            // The instance may be unreachable or overloaded right now, try again a bit later.
+            Err(error) => {
+                cache()
+                    .entry(self.cache_key().to_string())
+                    .or_default()
+                    .info_retry = Some(Instant::now() + SETTINGS_RETRY);
+
+                match error {
+                    TranslateError::Timeout => Err(error),
+                    _ => Ok(None),
+                }
+            }
+        }
+    }
+
+    /// Run `operation` on a client for each instance in turn, until one of them doesn't fail with a transient error.
+    async fn failover<T, F, Fut>(&self, operation: F) -> Result<T, TranslateError>
+    where
+        F: Fn(Client) -> Fut,
+        Fut: Future<Output = Result<T, TranslateError>>,
+    {
+        if self.fallbacks.is_empty() {
+            return operation(self.clone()).await;
+        }
+
+        let count = self.fallbacks.len() + 1;
+        let start = match &self.round_robin {
+            Some(turn) => turn.fetch_add(1, Ordering::Relaxed) % count,
+            None => 0,
+        };
+
+        // Instances that failed recently go last, in case every other one fails too.
+        let now = Instant::now();
+        let (mut order, down): (Vec<Client>, Vec<Client>) = (0..count)
+            .map(|i| self.instance((start + i) % count))
+            .partition(|client| {
+                cache()
+                    .get(client.cache_key())
+                    .and_then(|cached| cached.down_until)
+                    .filter(|until| *until > now)
+                    .is_none()
+            });
+        order.extend(down);
+
+        let mut last = None;
+
+        for client in order {
+            let key = client.cache_key().to_string();
+
+            match operation(client).await {
+                Err(error) if self.is_transient(&error) => {
+                    cache().entry(key).or_default().down_until =
+                        Some(Instant::now() + INSTANCE_COOLDOWN);
+                    last = Some(error);
+                }
+                result => {
+                    if let Some(cached) = cache().get_mut(&key) {
+                        cached.down_until = None;
+                    }
+
+                    return result;
+                }
+            }
+        }
+
+        match last {
+            Some(error) => Err(error),
+            None => unreachable!("a client has at least one instance"),
+        }
+    }
+
+    /// A copy of this client talking to the `index`th instance, the client's own URL being the first.
+    fn instance(&self, index: usize) -> Client {
+        let mut client = self.clone();
+        client.fallbacks = Vec::new();
+
+        if let Some(instance) = index.checked_sub(1).and_then(|i| self.fallbacks.get(i)) {
+            client.url = instance.url.clone();
+            client.key = instance.key.clone();
+        }
+
+        client
+    }
+
+    fn is_transient(&self, error: &TranslateError) -> bool {
+        match &self.retry {
+            Some(policy) => policy.retries(error),
+            None => RetryPolicy::is_transient(error),
+        }
+    }
+
+    fn cache_key(&self) -> &str {
+        self.url.trim_end_matches('/')
+    }
+
+    fn endpoint(&self, path: &str) -> String {
+        if self.url.ends_with('/') {
+            format!("{}{}", self.url, path)
+        } else {
+            format!("{}/{}", self.url, path)
+        }
+    }
+
+    async fn post<B: Serialize, R: DeserializeOwned>(
+        &self,
+        path: &str,
+        data: &B,
+        chars: usize,
+    ) -> Result<R, TranslateError> {
+        self.check_key().await?;
+
+        let body = match serde_json::to_vec(data) {
+            Ok(data) => data,
+            Err(error) => return Err(TranslateError::transport(error)),
+        };
+
+        let url = self.endpoint(path);
+
+        self.send(chars, Request::post(&url, "application/json", &body))
+            .await
+    }
+
+    async fn get<R: DeserializeOwned>(&self, path: &str) -> Result<R, TranslateError> {
+        let url = self.endpoint(path);
+
+        self.send(0, Request::get(&url)).await
+    }
+
+    /// Send `request` once, within the client's timeout.
+    async fn request(&self, request: Request<'_>) -> Result<Response, TranslateError> {
+        let request = Request {
+            timeout: self.timeout,
+            ..request
+        };
+
+        within(self.timeout, self.transport.send(request)).await
+    }
+
+    /// Send `request`, with `chars` characters of text, again as long as the retry policy allows it,
+    /// and decode its response.
+    async fn send<R: DeserializeOwned>(
+        &self,
+        chars: usize,
+        request: Request<'_>,
+    ) -> Result<R, TranslateError> {
+        self.retrying(chars, request, decode).await
+    }
+
+    /// Send `request`, with `chars` characters of text, within the rate limit and again as long as the retry
+    /// policy allows it. `read` turns a response into the result, or the error deciding whether to retry.
+    async fn retrying<T, F>(
+        &self,
+        chars: usize,
+        request: Request<'_>,
+        read: F,
+    ) -> Result<T, TranslateError>
+    where
+        F: Fn(Response) -> Result<T, TranslateError>,
+    {
+        let mut attempt = 1;
+
+        loop {
+            if let Some(limiter) = &self.limiter {
+                limiter.acquire(self.cache_key(), chars).await;
+            }
+
+            let res = self.request(request).await.and_then(&read);
+
+            let error = match res {
+                Ok(res) => return Ok(res),
+                Err(error) => error,
+            };
+
+            match self
+                .retry
+                .as_ref()
+                .and_then(|policy| policy.delay(attempt, &error))
+            {
+                Some(delay) => Delay::new(delay).await,
+                None => return Err(error),
+            }
+
+            attempt += 1;
+        }
+    }
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Self::new(DEFAULT_URL)
+    }
+}
+
+/// Decode the JSON body of a response, see [`models::decode`](models::decode).
+fn decode<R: DeserializeOwned>(res: Response) -> Result<R, TranslateError> {
+    let body = String::from_utf8_lossy(&res.body);
+    models::decode(res.status, res.retry_after.as_deref(), &body)
+}
+
+/// Fail with [`TranslateError::Timeout`](TranslateError::Timeout) if `future` isn't done after `timeout`.
+async fn within<T, F>(timeout: Option<Duration>, future: F) -> Result<T, TranslateError>
+where
+    F: Future<Output = Result<T, TranslateError>>,
+{
+    let timeout = match timeout {
+        Some(timeout) => timeout,
+        None => return future.await,
+    };
+
+    let future = pin!(future);
+
+    match future::select(future, Delay::new(timeout)).await {
+        Either::Left((result, _)) => result,
+        Either::Right(_) => Err(TranslateError::Timeout),
+    }
+}
+
+/// A group of consecutive texts that can be sent in a single request.
+#[derive(Debug, PartialEq)]
+enum Batch<'a, 'b> {
+    Texts(&'a [&'b str]),
+    /// A text that is too long to be sent in one piece.
+    Oversized(&'b str),
+}
+
+/// Split `inputs` into runs whose combined length stays under `limit` characters, keeping their order.
+fn pack<'a, 'b>(inputs: &'a [&'b str], limit: usize) -> Vec<Batch<'a, 'b>> {
+    let mut batches = Vec::new();
+    let mut start = 0;
+    let mut chars = 0;
+
+    for (i, input) in inputs.iter().enumerate() {
+        let count = input.chars().count();
+
+        if chars + count >= limit && start < i {
+            batches.push(Batch::Texts(&inputs[start..i]));
+            start = i;
+            chars = 0;
+        }
+
+        if count >= limit {
+            batches.push(Batch::Oversized(input));
+            start = i + 1;
+            continue;
+        }
+
+        chars += count;
+    }
+
+    if start < inputs.len() {
+        batches.push(Batch::Texts(&inputs[start..]));
+    }
+
+    batches
+}
+
+/// What is known about an instance, shared by every client talking to it.
+#[derive(Default)]
+struct Cached {
+    languages: Option<Vec<LanguageInfo>>,
+    /// `Some(None)` when the instance doesn't expose its settings.
+    info: Option<Option<InstanceInfo>>,
+    /// Until when the settings aren't asked for again, after the instance couldn't send them.
+    info_retry: Option<Instant>,
+    /// Until when a client with fallbacks should prefer other instances.
+    down_until: Option<Instant>,
+}
+
+fn cache() -> MutexGuard<'static, HashMap<String, Cached>> {
+    static INSTANCES: OnceLock<Mutex<HashMap<String, Cached>>> = OnceLock::new();
+
+    INSTANCES
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Drop what is cached about the instance at `url`.
+#[cfg(feature = "mock")]
+pub(crate) fn forget(url: &str) {
+    cache().remove(url.trim_end_matches('/'));
+}
+
+/// Build a `multipart/form-data` body with text `fields` and a single file, returning the boundary and the body.
+fn multipart(fields: &[(&str, &str)], name: &str, bytes: &[u8]) -> (String, Vec<u8>) {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|time| time.as_nanos())
+        .unwrap_or_default();
+    let boundary = format!("libretranslate-rs-{:x}", nanos);
+
+    let mut body = Vec::with_capacity(bytes.len() + 512);
+
+    for (field, value) in fields {
+        body.extend_from_slice(
+            format!(
+                "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+                boundary, field, value
+            )
+            .as_bytes(),
+        );
+    }
+
+    body.extend_from_slice(
+        format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{}\"\r\nContent-Type: application/octet-stream\r\n\r\n",
+            boundary,
+            name.replace('"', "")
+        )
+        .as_bytes(),
+    );
+    body.extend_from_slice(bytes);
+    body.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+
+    (boundary, body)
+}
+
+/// An instance a [`Client`](Client) can fall back to, see [`Client::fallback`](Client::fallback).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Instance {
+    url: String,
+    key: Option<String>,
+}
+
+impl Instance {
+    /// The instance at `url` (ex. "https://libretranslate.de/").
+    pub fn new<T: AsRef<str>>(url: T) -> Self {
+        Self {
+            url: url.as_ref().to_string(),
+            key: None,
+        }
+    }
+
+    /// Set the API key sent to this instance. The client's own key is only sent to the client's URL.
+    pub fn key<T: AsRef<str>>(mut self, key: T) -> Self {
+        self.key = Some(key.as_ref().to_string());
+        self
+    }
+
+    /// The base URL of the instance.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+/// A language candidate returned by [`Client::detect`](Client::detect), with a confidence from 0 to 100.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Detection {
+    pub language: Language,
+    pub confidence: f32,
+}
+
+/// A language served by an instance, as returned by [`Client::languages`](Client::languages).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LanguageInfo {
+    pub code: String,
+    pub name: String,
+    /// Codes of the languages this one can be translated to.
+    ///
+    /// Older instances don't list targets, every language can then be translated to every other one.
+    #[serde(default)]
+    pub targets: Vec<String>,
+}
+
+impl LanguageInfo {
+    /// Return the [`Language`](Language) with this code.
+    pub fn language(&self) -> Result<Language, LanguageError> {
+        Language::from_code(&self.code)
+    }
+
+    /// Whether this language can be translated to `target`.
+    pub fn supports(&self, target: Language) -> bool {
+        self.targets.iter().any(|code| code == target.as_code())
+    }
+}
+
+/// What an instance advertises about itself, as returned by [`Client::info`](Client::info).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InstanceInfo {
+    /// The number of characters accepted in a single request, `None` if there is no limit.
+    pub char_limit: Option<usize>,
+    /// Whether requests are refused without an API key.
+    pub key_required: bool,
+    /// Whether the instance hands out API keys.
+    pub api_keys: bool,
+    /// Whether [`Client::suggest`](Client::suggest) is accepted.
+    pub suggestions: bool,
+    /// Whether [`Client::translate_file`](Client::translate_file) is accepted.
+    pub files_translation: bool,
+    /// Extensions of the files that can be translated (ex. ".txt", ".docx").
+    pub supported_files_format: Vec<String>,
+}
+
+impl InstanceInfo {
+    /// Whether a file with this name can be translated, judging by its extension.
+    pub fn supports_file<P: AsRef<Path>>(&self, path: P) -> bool {
+        if !self.files_translation {
+            return false;
+        }
+
+        if self.supported_files_format.is_empty() {
+            return true;
+        }
+
+        match path.as_ref().extension().and_then(OsStr::to_str) {
+            Some(extension) => self.supported_files_format.iter().any(|format| {
+                format
+                    .trim_start_matches('.')
+                    .eq_ignore_ascii_case(extension)
+            }),
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pack_fills_batches_in_order() {
+        let inputs = ["one", "two", "three", "four"];
+
+        assert_eq!(
+            pack(&inputs, 9),
+            [
+                Batch::Texts(&["one", "two"]),
+                Batch::Texts(&["three"]),
+                Batch::Texts(&["four"]),
+            ]
+        );
+        assert_eq!(pack(&inputs, 100), [Batch::Texts(&inputs)]);
+        assert_eq!(pack(&[], 100), []);
+    }
+
+    #[test]
+    fn pack_isolates_oversized_texts() {
+        let inputs = ["a", "far too long", "b", "c"];
+
+        assert_eq!(
+            pack(&inputs, 5),
+            [
+                Batch::Texts(&["a"]),
+                Batch::Oversized("far too long"),
+                Batch::Texts(&["b", "c"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn pack_keeps_every_text() {
+        let mut rng = fastrand::Rng::with_seed(3);
+
+        for _ in 0..500 {
+            let inputs: Vec<String> = (0..rng.usize(0..30))
+                .map(|_| "x".repeat(rng.usize(0..12)))
+                .collect();
+            let inputs: Vec<&str> = inputs.iter().map(String::as_str).collect();
+            let limit = rng.usize(1..20);
+
+            let mut rest = &inputs[..];
+
+            for batch in pack(&inputs, limit) {
+                match batch {
+                    Batch::Texts(texts) => {
+                        let chars: usize = texts.iter().map(|text| text.len()).sum();
+
+                        assert!(!texts.is_empty());
+                        assert!(chars < limit);
+                        assert_eq!(texts, &rest[..texts.len()]);
+                        rest = &rest[texts.len()..];
+                    }
+                    Batch::Oversized(input) => {
+                        assert!(input.len() >= limit);
+                        assert_eq!(input, rest[0]);
+                        rest = &rest[1..];
+                    }
+                }
+            }
+
+            assert!(rest.is_empty());
+        }
+    }
+}
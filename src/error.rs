@@ -0,0 +1,130 @@
+use crate::Language;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The underlying error of a [`TranslateError`](TranslateError), returned by [`Error::source`](std::error::Error::source).
+pub type ErrorSource = Arc<dyn std::error::Error + Send + Sync>;
+
+/// Errors that could be outputed by [`translate`](crate::translate).
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum TranslateError {
+    /// The request couldn't be sent or the response couldn't be received.
+    TransportError(ErrorSource),
+    /// The response didn't have the expected shape.
+    DecodeError(ErrorSource),
+    /// The instance answered with an error status that has no more specific variant.
+    ServerError(u16, String),
+    /// The API key is missing or was refused by the instance.
+    KeyError(String),
+    /// The instance didn't answer in time, see [`Client::timeout`](crate::Client::timeout).
+    Timeout,
+    /// Too many requests were made, with how long the instance asks to wait if it said so.
+    RateLimitError(Option<Duration>),
+    /// The instance doesn't support a language of the request.
+    UnsupportedError(String),
+    /// The language couldn't be detected confidently enough.
+    DetectError,
+    /// The text is longer than the instance accepts.
+    LengthError,
+    /// The instance can't translate between these languages, checked before sending anything.
+    PairError(Language, Language),
+    /// The file to translate couldn't be read, written or sent.
+    FileError(ErrorSource),
+}
+
+impl TranslateError {
+    pub(crate) fn transport<E: Into<Box<dyn std::error::Error + Send + Sync>>>(error: E) -> Self {
+        TranslateError::TransportError(Arc::from(error.into()))
+    }
+
+    pub(crate) fn decode<E: Into<Box<dyn std::error::Error + Send + Sync>>>(error: E) -> Self {
+        TranslateError::DecodeError(Arc::from(error.into()))
+    }
+
+    pub(crate) fn file<E: Into<Box<dyn std::error::Error + Send + Sync>>>(error: E) -> Self {
+        TranslateError::FileError(Arc::from(error.into()))
+    }
+
+    /// Pick the variant matching an error response of the instance.
+    pub(crate) fn from_response(status: u16, retry_after: Option<&str>, message: String) -> Self {
+        let lowercase = message.to_lowercase();
+
+        match status {
+            401 => TranslateError::KeyError(message),
+            413 => TranslateError::LengthError,
+            429 => TranslateError::RateLimitError(
+                retry_after
+                    .and_then(|seconds| seconds.trim().parse().ok())
+                    .map(Duration::from_secs),
+            ),
+            400 | 403 if lowercase.contains("api key") => TranslateError::KeyError(message),
+            400 if lowercase.contains("not supported") => TranslateError::UnsupportedError(message),
+            400 if lowercase.contains("exceeds") => TranslateError::LengthError,
+            _ => TranslateError::ServerError(status, message),
+        }
+    }
+}
+
+impl std::error::Error for TranslateError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TranslateError::TransportError(error)
+            | TranslateError::DecodeError(error)
+            | TranslateError::FileError(error) => Some(&**error),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for TranslateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            TranslateError::TransportError(error) => {
+                write!(f, "HTTP request error: {}", error)
+            }
+            TranslateError::DecodeError(error) => {
+                write!(f, "JSON parsing error: {}", error)
+            }
+            TranslateError::ServerError(status, message) => {
+                write!(f, "Server error ({}): {}", status, message)
+            }
+            TranslateError::KeyError(message) => {
+                write!(f, "API key error: {}", message)
+            }
+            TranslateError::Timeout => {
+                write!(f, "The instance didn't answer in time")
+            }
+            TranslateError::RateLimitError(Some(retry_after)) => {
+                write!(
+                    f,
+                    "Too many requests, retry after {} seconds",
+                    retry_after.as_secs()
+                )
+            }
+            TranslateError::RateLimitError(None) => {
+                write!(f, "Too many requests")
+            }
+            TranslateError::UnsupportedError(message) => {
+                write!(f, "Unsupported language: {}", message)
+            }
+            TranslateError::DetectError => {
+                write!(f, "Language detection error")
+            }
+            TranslateError::LengthError => {
+                write!(f, "Requested text is too long")
+            }
+            TranslateError::PairError(source, target) => {
+                write!(
+                    f,
+                    "Translating from {} to {} is not supported by this instance",
+                    source.as_pretty(),
+                    target.as_pretty()
+                )
+            }
+            TranslateError::FileError(error) => {
+                write!(f, "File error: {}", error)
+            }
+        }
+    }
+}
@@ -0,0 +1,307 @@
+use std::str::FromStr;
+
+/// The longest language code a [`LanguageCode`](LanguageCode) can hold.
+const MAX_CODE_LEN: usize = 15;
+
+/// Generate [`Language`](Language) and its conversions from a single table of `Variant => "code", "Pretty Name";` rows.
+macro_rules! languages {
+    ($($(#[$meta:meta])* $variant:ident => $code:literal, $pretty:literal;)*) => {
+        /// Languages that can used for input and output of the [`translate`](crate::translate) function.
+        ///
+        /// Languages added to LibreTranslate after this crate was released can still be used through
+        /// [`Language::Other`](Language::Other), see [`Language::from_code`](Language::from_code).
+        #[derive(Debug, Clone, PartialEq, Eq, Copy, Hash, Default)]
+        #[non_exhaustive]
+        pub enum Language {
+            Detect,
+            $($(#[$meta])* $variant,)*
+            Other(LanguageCode),
+        }
+
+        const LANGUAGES: &[Language] = &[$(Language::$variant,)*];
+
+        impl Language {
+            /// Return the language with the language code name. (ex. "ar", "de")
+            pub fn as_code(&self) -> &str {
+                match self {
+                    Language::Detect => "auto",
+                    $(Language::$variant => $code,)*
+                    Language::Other(code) => code.as_str(),
+                }
+            }
+
+            /// Return the Language with the full English name. (ex. "Arabic", "German")
+            ///
+            /// [`Language::Other`](Language::Other) has no known name and returns its code.
+            pub fn as_pretty(&self) -> &str {
+                match self {
+                    Language::Detect => "Detected",
+                    $(Language::$variant => $pretty,)*
+                    Language::Other(code) => code.as_str(),
+                }
+            }
+        }
+    };
+}
+
+languages! {
+    // TODO: Get locale from user to set Language::default().
+    #[default]
+    English => "en", "English";
+    Albanian => "sq", "Albanian";
+    Arabic => "ar", "Arabic";
+    Azerbaijani => "az", "Azerbaijani";
+    Basque => "eu", "Basque";
+    Bengali => "bn", "Bengali";
+    Bulgarian => "bg", "Bulgarian";
+    Catalan => "ca", "Catalan";
+    Chinese => "zh", "Chinese";
+    ChineseTraditional => "zt", "Chinese (traditional)";
+    Czech => "cs", "Czech";
+    Danish => "da", "Danish";
+    Dutch => "nl", "Dutch";
+    Esperanto => "eo", "Esperanto";
+    Estonian => "et", "Estonian";
+    Finnish => "fi", "Finnish";
+    French => "fr", "French";
+    Galician => "gl", "Galician";
+    German => "de", "German";
+    Greek => "el", "Greek";
+    Hebrew => "he", "Hebrew";
+    Hindi => "hi", "Hindi";
+    Hungarian => "hu", "Hungarian";
+    Indonesian => "id", "Indonesian";
+    Irish => "ga", "Irish";
+    Italian => "it", "Italian";
+    Japanese => "ja", "Japanese";
+    Korean => "ko", "Korean";
+    Kyrgyz => "ky", "Kyrgyz";
+    Latvian => "lv", "Latvian";
+    Lithuanian => "lt", "Lithuanian";
+    Malay => "ms", "Malay";
+    Norwegian => "nb", "Norwegian";
+    Persian => "fa", "Persian";
+    Polish => "pl", "Polish";
+    Portuguese => "pt", "Portuguese";
+    BrazilianPortuguese => "pb", "Portuguese (Brazil)";
+    Romanian => "ro", "Romanian";
+    Russian => "ru", "Russian";
+    Serbian => "sr", "Serbian";
+    Slovak => "sk", "Slovak";
+    Slovenian => "sl", "Slovenian";
+    Spanish => "es", "Spanish";
+    Swedish => "sv", "Swedish";
+    Tagalog => "tl", "Tagalog";
+    Thai => "th", "Thai";
+    Turkish => "tr", "Turkish";
+    Ukrainian => "uk", "Ukrainian";
+    Urdu => "ur", "Urdu";
+    Vietnamese => "vi", "Vietnamese";
+}
+
+impl Language {
+    /// Every language that can be translated, which is all of them except [`Language::Detect`](Language::Detect).
+    pub fn all() -> &'static [Language] {
+        LANGUAGES
+    }
+
+    /// Create a Language from &str like "en" or "French". Case Doesn't matter.
+    pub fn from<T: AsRef<str>>(s: T) -> Result<Self, LanguageError> {
+        Self::from_str(s.as_ref())
+    }
+
+    /// Create a Language from a language code like "en" or "zh-Hant".
+    ///
+    /// Unlike [`from`](Language::from), codes this crate doesn't know about are accepted
+    /// as [`Language::Other`](Language::Other) as long as they look like a language code.
+    pub fn from_code<T: AsRef<str>>(code: T) -> Result<Self, LanguageError> {
+        let code = code.as_ref();
+
+        if code.eq_ignore_ascii_case("auto") {
+            return Ok(Language::Detect);
+        }
+
+        match LANGUAGES
+            .iter()
+            .find(|language| language.as_code().eq_ignore_ascii_case(code))
+        {
+            Some(language) => Ok(*language),
+            None => Ok(Language::Other(LanguageCode::new(code)?)),
+        }
+    }
+
+    /// Create a Language from a [`LanguageIdentifier`](unic_langid::LanguageIdentifier).
+    #[cfg(feature = "unicode_langid")]
+    pub fn from_unic_langid(s: unic_langid::LanguageIdentifier) -> Result<Self, LanguageError> {
+        match LANGUAGES
+            .iter()
+            .find(|language| language.as_code() == s.language.as_str())
+        {
+            Some(language) => Ok(*language),
+            None => Err(LanguageError::FormatError("Unknown Language".to_string())),
+        }
+    }
+}
+
+impl FromStr for Language {
+    type Err = LanguageError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("auto") {
+            return Ok(Language::Detect);
+        }
+
+        match LANGUAGES.iter().find(|language| {
+            language.as_code().eq_ignore_ascii_case(s)
+                || language.as_pretty().eq_ignore_ascii_case(s)
+        }) {
+            Some(language) => Ok(*language),
+            None => Err(LanguageError::FormatError(s.to_string())),
+        }
+    }
+}
+
+impl std::fmt::Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.as_code())
+    }
+}
+
+impl serde::Serialize for Language {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_code())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Language {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        Language::from_code(code).map_err(serde::de::Error::custom)
+    }
+}
+
+/// A validated language code that has no [`Language`](Language) variant yet (ex. "zh-Hant").
+///
+/// It is stored inline so [`Language`](Language) stays `Copy`. Codes are compared without regard to case, like
+/// [`Language::from_code`](Language::from_code) looks them up, and keep the case they were written with.
+#[derive(Clone, Copy, Eq)]
+pub struct LanguageCode {
+    len: u8,
+    bytes: [u8; MAX_CODE_LEN],
+}
+
+impl LanguageCode {
+    /// Create a code from ASCII letters, digits and dashes, between 2 and 15 characters long.
+    ///
+    /// Codes of a [`Language`](Language) variant are refused with
+    /// [`LanguageError::VariantError`](LanguageError::VariantError), so a language has a single value.
+    pub fn new<T: AsRef<str>>(code: T) -> Result<Self, LanguageError> {
+        let code = code.as_ref();
+
+        let valid = (2..=MAX_CODE_LEN).contains(&code.len())
+            && code.starts_with(|c: char| c.is_ascii_alphabetic())
+            && code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+
+        if !valid {
+            return Err(LanguageError::FormatError(code.to_string()));
+        }
+
+        if let Some(language) = std::iter::once(&Language::Detect)
+            .chain(LANGUAGES)
+            .find(|language| language.as_code().eq_ignore_ascii_case(code))
+        {
+            return Err(LanguageError::VariantError(*language));
+        }
+
+        let mut bytes = [0; MAX_CODE_LEN];
+        bytes[..code.len()].copy_from_slice(code.as_bytes());
+
+        Ok(Self {
+            len: code.len() as u8,
+            bytes,
+        })
+    }
+
+    /// Return the code as a `&str`.
+    pub fn as_str(&self) -> &str {
+        // Only ASCII is ever stored, see `LanguageCode::new`.
+        std::str::from_utf8(&self.bytes[..self.len as usize]).unwrap_or_default()
+    }
+}
+
+impl PartialEq for LanguageCode {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str().eq_ignore_ascii_case(other.as_str())
+    }
+}
+
+impl std::hash::Hash for LanguageCode {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        for byte in self.as_str().bytes() {
+            state.write_u8(byte.to_ascii_lowercase());
+        }
+
+        state.write_u8(0xff);
+    }
+}
+
+impl FromStr for LanguageCode {
+    type Err = LanguageError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl std::fmt::Debug for LanguageCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self.as_str())
+    }
+}
+
+impl std::fmt::Display for LanguageCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl serde::Serialize for LanguageCode {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for LanguageCode {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        LanguageCode::new(code).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Errors that could be outputed by a [`Language`](Language).
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub enum LanguageError {
+    FormatError(String),
+    /// The code is the one of this variant, which has to be used instead of [`Language::Other`](Language::Other).
+    VariantError(Language),
+}
+
+impl std::error::Error for LanguageError {}
+
+impl std::fmt::Display for LanguageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            LanguageError::FormatError(error) => {
+                write!(f, "Unknown Language: {}", error)
+            }
+            LanguageError::VariantError(language) => {
+                write!(
+                    f,
+                    "{} has its own variant: {:?}",
+                    language.as_code(),
+                    language
+                )
+            }
+        }
+    }
+}
@@ -1,13 +1,13 @@
 //! # libretranslate-rs
-//!! [![Crates.io](https:img.shields.io/crates/v/libretranslate.svg)](https://crates.io/crates/libretranslate)
+//! [![Crates.io](https://img.shields.io/crates/v/libretranslate.svg)](https://crates.io/crates/libretranslate)
 //! [![Crates.io](https://img.shields.io/crates/d/libretranslate)](https://crates.io/crates/libretranslate)
 //! [![API](https://docs.rs/libretranslate/badge.svg)](https://docs.rs/libretranslate)
 //! [![Gitpod ready-to-code](https://img.shields.io/badge/Gitpod-ready--to--code-blue?logo=gitpod)](https://gitpod.io/#https://github.com/grantshandy/libretranslate-rs)
 //! ![GitHub Workflow Status](https://img.shields.io/github/workflow/status/grantshandy/libretranslate-rs/Rust)
 //!
 //! A LibreTranslate API client for Rust.
-//! ```
-//! libretranslate = "0.5"
+//! ```toml
+//! libretranslate = "0.6"
 //! ```
 //!
 //! `libretranslate` allows you to use open source machine translation in your projects through an easy to use API that connects to the official [webpage](https://libretranslate.com/).
@@ -16,7 +16,7 @@
 //! `libretranslate` is an async library, so you'll have to use an async runtime like [`tokio`](https://crates.io/crates/tokio) or [`async-std`](https://crates.io/crates/async-std).
 //!
 //! All translations are done through the [`translate`](crate::translate) function:
-//! ```rust
+//! ```rust,no_run
 //! use libretranslate::{translate, Language};
 //!
 //! #[tokio::main]
@@ -33,7 +33,7 @@
 //! ```
 //!
 //! Output:
-//! ```
+//! ```text
 //! Input French: le texte français.
 //! Output English: the French text.
 //! ```
@@ -42,7 +42,7 @@
 //!
 //! ## Language Detection
 //! Here's a simple example.
-//! ```rust
+//! ```rust,no_run
 //! use libretranslate::{translate, Language};
 //!
 //! #[tokio::main]
@@ -58,13 +58,28 @@
 //! ```
 //!
 //! Output:
-//! ```
+//! ```text
 //! Input French: le texte français.
 //! Output English: the French text.
 //! ```
 //!
 //! [See In Examples Folder](https://github.com/grantshandy/libretranslate-rs/blob/main/examples/detect.rs)
 //!
+//! If you only need the language, [`detect`](crate::detect) asks the instance without translating anything.
+//! It returns every candidate ranked by confidence:
+//! ```rust,no_run
+//! use libretranslate::detect;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let candidates = detect("le texte français.", None).await.unwrap();
+//!
+//!     for candidate in candidates {
+//!         println!("{}: {}", candidate.language.as_pretty(), candidate.confidence);
+//!     }
+//! }
+//! ```
+//!
 //! ## Language Functionality
 //! The `Language` enum has a lot of functionality so you can create a `Language` from all sorts of different user inputs.
 //!
@@ -72,8 +87,12 @@
 //!
 //! `Language` also implements `FromStr` so you can create a `Language` using text like "en", or "English" (case doesn't matter). You can do this by either using `Language::from()` or `.parse::<Language>()`.
 //!
+//! If an instance serves a language this crate doesn't have a variant for yet, `Language::from_code()` accepts its code as `Language::Other`.
+//!
 //! Here's a simple example.
 //! ```rust
+//! use libretranslate::Language;
+//!
 //! let lang = Language::English;
 //! let lang_parse = "english".parse::<Language>().unwrap();
 //!
@@ -88,7 +107,7 @@
 //! The trait `Translate` implements [`AsRef<str>`](https://doc.rust-lang.org/std/convert/trait.AsRef.html), meaning that any `&str` or `String` can be translated into any other language.
 //!
 //! Here's a simple example.
-//! ```rust
+//! ```rust,no_run
 //! use libretranslate::{Language, Translate};
 //!
 //! #[tokio::main]
@@ -105,36 +124,238 @@
 //! ```
 //!
 //! Output:
-//! ```
+//! ```text
 //! Output: "Dies ist Text, geschrieben auf einem Computer, in Englisch."
 //! ```
 //!
 //! [See In Examples Folder](https://github.com/grantshandy/libretranslate-rs/blob/main/examples/method.rs)
 //!
+//! ## Client
+//! If you make a lot of requests to the same instance, create a [`Client`](crate::Client) once and reuse it.
+//! It keeps the URL, API key, default languages and the underlying HTTP connections in one place.
+//!
+//! Here's a simple example.
+//! ```rust,no_run
+//! use libretranslate::{Client, Language};
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let client = Client::new("https://libretranslate.de/")
+//!         .from_lang(Language::English)
+//!         .to_lang(Language::French);
+//!
+//!     let data = client.translate("Hello world!").await.unwrap();
+//!     println!("Output {}: {}", data.target.as_pretty(), data.output);
+//!
+//!     let data = client.text("Hello world!").to_lang(Language::German).translate().await.unwrap();
+//!     println!("Output {}: {}", data.target.as_pretty(), data.output);
+//! }
+//! ```
+//!
+//! [See In Examples Folder](https://github.com/grantshandy/libretranslate-rs/blob/main/examples/client.rs)
+//!
+//! ## Translator Trait
+//! [`Client`](crate::Client) and [`Pseudo`](crate::Pseudo) both implement the object-safe [`Translator`](crate::Translator)
+//! trait. Code can depend on `&dyn Translator` and be given a client, a pseudo-localizer or your own implementation (a
+//! cache, a fake or another engine). [`TranslationBuilder`](crate::TranslationBuilder) and [`Query`](crate::Query) are
+//! pointed at one with their `translator` method.
+//! ```rust
+//! use libretranslate::{Language, Pseudo, Translate, Translator};
+//!
+//! async fn greet(translator: &dyn Translator) -> String {
+//!     "Hello world!".to_lang(Language::French).translator(translator).translate().await.unwrap()
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     // Or `Client::new("https://libretranslate.de/")`.
+//!     let translator = Pseudo::new();
+//!
+//!     println!("Output: \"{}\"", greet(&translator).await);
+//! }
+//! ```
+//!
+//! [See In Examples Folder](https://github.com/grantshandy/libretranslate-rs/blob/main/examples/translator.rs)
+//!
+//! ## HTTP Backends
+//! Requests are sent with [surf](https://docs.rs/surf) by default. If your project already uses another HTTP client,
+//! you can switch to it with cargo features:
+//! ```toml
+//! [dependencies]
+//! libretranslate = { version = "0.6", default-features = false, features = ["reqwest-rustls"] }
+//! ```
+//!
+//! | Feature | Backend |
+//! | --- | --- |
+//! | `surf` (default) | [surf](https://docs.rs/surf), on curl |
+//! | `reqwest` | [reqwest](https://docs.rs/reqwest), on tokio, add `reqwest-rustls` or `reqwest-native-tls` for HTTPS |
+//! | `ureq` | [ureq](https://docs.rs/ureq), blocking |
+//!
+//! ## Blocking
+//! With the `blocking` feature, the `blocking` module translates without an async runtime, on the blocking `ureq` backend.
+//! ```rust,ignore
+//! use libretranslate::{blocking, Language};
+//!
+//! fn main() {
+//!     let data = blocking::translate(Language::English, Language::French, "Hello world!", None).unwrap();
+//!
+//!     println!("Output: \"{}\"", data.output);
+//! }
+//! ```
+//!
+//! [See In Examples Folder](https://github.com/grantshandy/libretranslate-rs/blob/main/examples/blocking.rs)
+//!
+//! ## Pseudo-localization
+//! [`Client::pseudo`](crate::Client::pseudo) makes a client that translates without a server, into accented, padded
+//! and bracketed text ("This is text" becomes "[Ŧĥîš îš ţéxţ ~~~]"). It shows where a user interface truncates text or
+//! has hard-coded strings. Placeholders like `{name}` or `%d` are kept, and texts translated to Arabic are displayed
+//! right-to-left.
+//! ```rust
+//! use libretranslate::{Client, Language, Pseudo};
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let client = Client::pseudo(Pseudo::new().expansion(0.4))
+//!         .from_lang(Language::English)
+//!         .to_lang(Language::Arabic);
+//!
+//!     let data = client.translate("Welcome back, {name}!").await.unwrap();
+//!
+//!     println!("Output: \"{}\"", data.output);
+//! }
+//! ```
+//!
+//! [See In Examples Folder](https://github.com/grantshandy/libretranslate-rs/blob/main/examples/pseudo.rs)
+//!
+//! ## Testing
+//! With the `mock` feature, [`mock::MockServer`](crate::mock::MockServer) runs a LibreTranslate instance inside your
+//! tests. Pass its URL to the crate like any other instance, and script its translations, errors, latency or rate limiting.
+//! ```rust,ignore
+//! use libretranslate::mock::MockServer;
+//! use libretranslate::{Language, TranslationBuilder};
+//!
+//! #[tokio::test]
+//! async fn greets_in_french() {
+//!     let server = MockServer::start();
+//!     server.translation("Hello world!", "Bonjour le monde !");
+//!
+//!     let data = TranslationBuilder::new()
+//!         .url(server.url())
+//!         .from_lang(Language::English)
+//!         .to_lang(Language::French)
+//!         .text("Hello world!")
+//!         .translate()
+//!         .await
+//!         .unwrap();
+//!
+//!     assert_eq!(data.output, "Bonjour le monde !");
+//! }
+//! ```
+//!
+//! With the `cassette` feature, [`Client::record`](crate::Client::record) writes the requests a client sends and the
+//! responses it gets to a JSON file, and [`Client::replay`](crate::Client::replay) answers requests from that file
+//! instead of the network. Requests that weren't recorded fail, so a test can't reach a real instance by accident.
+//! ```rust,ignore
+//! // Once, against a real instance:
+//! let client = Client::new("https://libretranslate.com/").record("tests/greeting.json");
+//!
+//! // In the tests, offline:
+//! let client = Client::new("https://libretranslate.com/").replay("tests/greeting.json")?;
+//! ```
+//!
 //! ## Available Languages
-//! - English
-//! - Arabic
-//! - Chinese
-//! - French
-//! - German
-//! - Italian
-//! - Japanese
-//! - Portuguese
-//! - Russian
-//! - Spanish
-//! - Polish
+//! Every language LibreTranslate serves has a [`Language`](crate::Language) variant, from Albanian to Vietnamese.
+//! You can go through all of them with [`Language::all()`](crate::Language::all):
+//! ```rust
+//! use libretranslate::Language;
+//!
+//! for language in Language::all() {
+//!     println!("{}: {}", language.as_code(), language.as_pretty());
+//! }
+//! ```
 //!
 
-use serde_json::Value;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+mod chunk;
+mod client;
+mod error;
+mod language;
+mod limit;
+#[cfg(feature = "mock")]
+pub mod mock;
+mod models;
+mod pseudo;
+mod retry;
+mod translator;
+mod transport;
+
+pub use client::{Client, Detection, Instance, InstanceInfo, LanguageInfo};
+pub use error::{ErrorSource, TranslateError};
+pub use language::{Language, LanguageCode, LanguageError};
+pub use limit::RateLimit;
+pub use pseudo::Pseudo;
+pub use retry::RetryPolicy;
+pub use translator::Translator;
+
+use std::time::Duration;
+
+const DEFAULT_URL: &str = "https://libretranslate.com/";
 
 /// Data that is output by the [`translate`](translate) function.
-#[derive(Debug, Clone, PartialEq, Hash)]
+///
+/// When translating from [`Language::Detect`](Language::Detect), `source` is the language detected by the
+/// instance and `confidence` how sure it is about it (from 0 to 100).
+#[derive(Debug, Clone, PartialEq)]
 pub struct Translation {
+    /// The endpoint that served the translation, which tells the instance when a client has fallbacks.
     pub url: String,
     pub source: Language,
     pub target: Language,
     pub input: String,
     pub output: String,
+    pub confidence: Option<f32>,
+    /// Other possible translations, if some were requested.
+    pub alternatives: Vec<String>,
+}
+
+impl Translation {
+    /// Submit `corrected` as a better translation of this one's input through `client`.
+    /// See [`Client::suggest`](Client::suggest).
+    pub async fn suggest<T: AsRef<str>>(
+        &self,
+        client: &Client,
+        corrected: T,
+    ) -> Result<(), TranslateError> {
+        client
+            .suggest(
+                self.input.as_str(),
+                corrected.as_ref(),
+                self.source,
+                self.target,
+            )
+            .await
+    }
+}
+
+/// The format of the text sent for translation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Format {
+    /// Plain text.
+    #[default]
+    Text,
+    /// HTML, the markup is kept as is and only the text inside it is translated.
+    Html,
+}
+
+impl Format {
+    /// Return the name LibreTranslate uses for the format. (ex. "text", "html")
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Format::Text => "text",
+            Format::Html => "html",
+        }
+    }
 }
 
 /// Translate text between two [`Language`](Language).
@@ -144,16 +365,16 @@ pub async fn translate<T: AsRef<str>>(
     input: T,
     key: Option<T>,
 ) -> Result<Translation, TranslateError> {
-    let url = "https://libretranslate.com/";
-
     let key: Option<String> = key.map(|data| data.as_ref().to_string());
 
-    let data = translate_url(source, target, input.as_ref(), url, key).await?;
+    let data = translate_url(source, target, input.as_ref(), DEFAULT_URL, key).await?;
 
     Ok(data)
 }
 
 /// Translate using a custom URL.
+///
+/// This creates a new [`Client`](Client) on every call, use one directly if you make many requests.
 pub async fn translate_url<T: AsRef<str>>(
     source: Language,
     target: Language,
@@ -161,266 +382,40 @@ pub async fn translate_url<T: AsRef<str>>(
     url: T,
     key: Option<String>,
 ) -> Result<Translation, TranslateError> {
-    let complete_url: String;
-
-    if url.as_ref().ends_with('/') {
-        complete_url = format!("{}translate", url.as_ref());
-    } else {
-        complete_url = format!("{}/translate", url.as_ref());
-    };
-
-    if input.as_ref().chars().count() >= 5000 {
-        return Err(TranslateError::LengthError);
-    };
-
-    let data: Value = match key {
-        Some(key) => {
-            serde_json::json!({
-                "q": input.as_ref(),
-                "source": source.as_code(),
-                "target": target.as_code(),
-                "api_key": key,
-            })
-        }
-        None => {
-            serde_json::json!({
-                "q": input.as_ref(),
-                "source": source.as_code(),
-                "target": target.as_code(),
-            })
-        }
-    };
-
-    let body = match surf::http::Body::from_json(&data) {
-        Ok(data) => data,
-        Err(error) => return Err(TranslateError::HttpError(error.to_string())),
-    };
-
-    let url = complete_url.clone();
+    let mut client = Client::new(url);
 
-    let res = match surf::post(complete_url).body(body).recv_string().await {
-        Ok(data) => data,
-        Err(error) => return Err(TranslateError::HttpError(error.to_string())),
+    if let Some(key) = key {
+        client = client.key(key);
     };
 
-    let parsed_json: Value = match serde_json::from_str(&res) {
-        Ok(parsed_json) => parsed_json,
-        Err(error) => {
-            return Err(TranslateError::ParseError(error.to_string()));
-        }
-    };
-
-    if let Value::String(error) = &parsed_json["error"] {
-        return Err(TranslateError::ParseError(error.to_string()));
-    }
-
-    let output = match &parsed_json["translatedText"] {
-        Value::String(output) => output,
-        _ => {
-            return Err(TranslateError::ParseError(String::from(
-                "Unable to find translatedText in parsed JSON",
-            )))
-        }
-    };
-
-    let input = input.as_ref().to_string();
-    let output = output.to_string();
-
-    Ok(Translation {
-        url,
-        source,
-        target,
-        input,
-        output,
-    })
-}
-
-use std::str::FromStr;
-
-/// Languages that can used for input and output of the [`translate`](crate::translate) function.
-#[derive(Debug, Clone, PartialEq, Copy, Hash)]
-pub enum Language {
-    Detect,
-    English,
-    Arabic,
-    Chinese,
-    French,
-    German,
-    Italian,
-    Japanese,
-    Portuguese,
-    Russian,
-    Spanish,
-    Polish,
+    client.translate_text(source, target, input.as_ref()).await
 }
 
-impl Language {
-    /// Return the language with the language code name. (ex. "ar", "de")
-    pub fn as_code(&self) -> &'static str {
-        match self {
-            Language::Detect => "auto",
-            Language::English => "en",
-            Language::Arabic => "ar",
-            Language::Chinese => "zh",
-            Language::French => "fr",
-            Language::German => "de",
-            Language::Italian => "it",
-            Language::Japanese => "ja",
-            Language::Portuguese => "pt",
-            Language::Russian => "ru",
-            Language::Spanish => "es",
-            Language::Polish => "pl",
-        }
-    }
-
-    /// Return the Language with the full English name. (ex. "Arabic", "German")
-    pub fn as_pretty(&self) -> &'static str {
-        match self {
-            Language::Detect => "Detected",
-            Language::English => "English",
-            Language::Arabic => "Arabic",
-            Language::Chinese => "Chinese",
-            Language::French => "French",
-            Language::German => "German",
-            Language::Italian => "Italian",
-            Language::Japanese => "Japanese",
-            Language::Portuguese => "Portuguese",
-            Language::Russian => "Russian",
-            Language::Spanish => "Spanish",
-            Language::Polish => "pl",
-        }
-    }
-
-    /// Create a Language from &str like "en" or "French". Case Doesn't matter.
-    pub fn from<T: AsRef<str>>(s: T) -> Result<Self, LanguageError> {
-        return Self::from_str(s.as_ref());
-    }
-
-    /// Create a Language from a [`LanguageIdentifier`](unic_langid::LanguageIdentifier).
-    #[cfg(feature = "unicode_langid")]
-    pub fn from_unic_langid(s: unic_langid::LanguageIdentifier) -> Result<Self, LanguageError> {
-        match s.language.as_str() {
-            "en" => Ok(Language::English),
-            "ar" => Ok(Language::Arabic),
-            "zh" => Ok(Language::Chinese),
-            "fr" => Ok(Language::French),
-            "de" => Ok(Language::German),
-            "it" => Ok(Language::Italian),
-            "pt" => Ok(Language::Portuguese),
-            "ru" => Ok(Language::Russian),
-            "es" => Ok(Language::Spanish),
-            "ja" => Ok(Language::Japanese),
-            "pl" => Ok(Language::Polish),
-            &_ => Err(LanguageError::FormatError("Unknown Language".to_string())),
-        }
-    }
-}
-
-// TODO: Get locale from user to set Language::default().
-impl Default for Language {
-    fn default() -> Self {
-        Language::English
-    }
-}
-
-impl FromStr for Language {
-    type Err = LanguageError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_string().to_lowercase().as_str() {
-            "en" => Ok(Language::English),
-            "ar" => Ok(Language::Arabic),
-            "zh" => Ok(Language::Chinese),
-            "fr" => Ok(Language::French),
-            "de" => Ok(Language::German),
-            "it" => Ok(Language::Italian),
-            "pt" => Ok(Language::Portuguese),
-            "ru" => Ok(Language::Russian),
-            "es" => Ok(Language::Spanish),
-            "ja" => Ok(Language::Japanese),
-            "pl" => Ok(Language::Polish),
-            "english" => Ok(Language::English),
-            "arabic" => Ok(Language::Arabic),
-            "chinese" => Ok(Language::Chinese),
-            "french" => Ok(Language::French),
-            "german" => Ok(Language::German),
-            "italian" => Ok(Language::Italian),
-            "portuguese" => Ok(Language::Portuguese),
-            "russian" => Ok(Language::Russian),
-            "spanish" => Ok(Language::Spanish),
-            "japanese" => Ok(Language::Japanese),
-            "polish" => Ok(Language::Polish),
-            "auto" => Ok(Language::Detect),
-            &_ => Err(LanguageError::FormatError(s.to_string())),
-        }
-    }
-}
-
-impl std::fmt::Display for Language {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        match self {
-            Language::Detect => write!(f, "auto"),
-            Language::English => write!(f, "en"),
-            Language::Arabic => write!(f, "ar"),
-            Language::Chinese => write!(f, "zh"),
-            Language::French => write!(f, "fr"),
-            Language::German => write!(f, "de"),
-            Language::Italian => write!(f, "it"),
-            Language::Portuguese => write!(f, "pt"),
-            Language::Russian => write!(f, "ru"),
-            Language::Spanish => write!(f, "es"),
-            Language::Japanese => write!(f, "ja"),
-            Language::Polish => write!(f, "pl"),
-        }
-    }
-}
-
-/// Errors that could be outputed by a [`Language`](Language).
-#[derive(Debug, Clone, PartialEq, Hash)]
-pub enum LanguageError {
-    FormatError(String),
-}
+/// Detect the language of some text, ranked from the most to the least confident candidate.
+pub async fn detect<T: AsRef<str>>(
+    input: T,
+    key: Option<T>,
+) -> Result<Vec<Detection>, TranslateError> {
+    let key: Option<String> = key.map(|data| data.as_ref().to_string());
 
-impl std::error::Error for LanguageError {}
+    let data = detect_url(input.as_ref(), DEFAULT_URL, key).await?;
 
-impl std::fmt::Display for LanguageError {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        match self {
-            LanguageError::FormatError(error) => {
-                write!(f, "Unknown Language: {}", error)
-            }
-        }
-    }
+    Ok(data)
 }
 
-/// Errors that could be outputed by [`translate`](crate::translate).
-#[derive(Debug, Clone, PartialEq, Hash)]
-pub enum TranslateError {
-    HttpError(String),
-    ParseError(String),
-    DetectError,
-    LengthError,
-}
+/// Detect the language of some text using a custom URL.
+pub async fn detect_url<T: AsRef<str>>(
+    input: T,
+    url: T,
+    key: Option<String>,
+) -> Result<Vec<Detection>, TranslateError> {
+    let mut client = Client::new(url);
 
-impl std::error::Error for TranslateError {}
+    if let Some(key) = key {
+        client = client.key(key);
+    };
 
-impl std::fmt::Display for TranslateError {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        match self {
-            TranslateError::HttpError(error) => {
-                write!(f, "HTTP request error: {}", error.to_string())
-            }
-            TranslateError::ParseError(error) => {
-                write!(f, "JSON parsing error: {}", error.to_string())
-            }
-            TranslateError::DetectError => {
-                write!(f, "Language detection error")
-            }
-            TranslateError::LengthError => {
-                write!(f, "Requested text is too long")
-            }
-        }
-    }
+    client.detect(input).await
 }
 
 /// A struct created by a [`Translate`](Translate) that can be translated using the translate method.
@@ -429,6 +424,9 @@ pub struct Query<'a> {
     pub text: &'a str,
     pub source: Language,
     pub target: Language,
+    pub client: Option<&'a Client>,
+    pub format: Option<Format>,
+    pub translator: Option<&'a dyn Translator>,
 }
 
 impl<'a> Query<'a> {
@@ -442,64 +440,139 @@ impl<'a> Query<'a> {
         self
     }
 
-    pub fn url(mut self, url: &'a str) -> Query {
+    pub fn url(mut self, url: &'a str) -> Query<'a> {
         self.url = url;
         self
     }
 
+    /// Send the query through a [`Client`](Client) instead of the URL.
+    pub fn client(mut self, client: &'a Client) -> Query<'a> {
+        self.url = client.url();
+        self.client = Some(client);
+        self.translator = None;
+        self
+    }
+
+    /// Translate the query with any [`Translator`](Translator) instead of the URL. The format is then ignored.
+    pub fn translator(mut self, translator: &'a dyn Translator) -> Query<'a> {
+        self.translator = Some(translator);
+        self.client = None;
+        self
+    }
+
+    pub fn format(mut self, format: Format) -> Query<'a> {
+        self.format = Some(format);
+        self
+    }
+
     pub async fn translate(self) -> Result<String, TranslateError> {
-        let res = crate::translate_url(self.source, self.target, self.text, self.url, None).await?;
+        match self.translator {
+            Some(translator) => self.translate_with(translator).await,
+            None => {
+                let client = self.prepared_client();
+
+                self.translate_with(&client).await
+            }
+        }
+    }
+
+    /// The query's client, or one for its URL, with the query's options applied.
+    pub(crate) fn prepared_client(&self) -> Client {
+        let client = match self.client {
+            Some(client) => client.clone(),
+            None => Client::new(self.url),
+        };
+
+        match self.format {
+            Some(format) => client.format(format),
+            None => client,
+        }
+    }
+
+    pub(crate) async fn translate_with(
+        self,
+        translator: &dyn Translator,
+    ) -> Result<String, TranslateError> {
+        let res = translator
+            .translate(self.source, self.target, self.text)
+            .await?;
+
         Ok(res.output)
     }
 }
 
 /// Translate text from a [`String`](std::string::String) or [`str`](std::str) (anything that implements [`AsRef<str>`](std::convert::AsRef)).
 pub trait Translate {
-    fn to_lang(&self, language: Language) -> Query;
-    fn from_lang(&self, language: Language) -> Query;
+    fn to_lang(&self, language: Language) -> Query<'_>;
+    #[allow(clippy::wrong_self_convention)]
+    fn from_lang(&self, language: Language) -> Query<'_>;
 }
 
 impl<T> Translate for T
 where
     T: AsRef<str>,
 {
-    fn to_lang(&self, language: Language) -> Query {
+    fn to_lang(&self, language: Language) -> Query<'_> {
         Query {
-            url: "https://libretranslate.com/",
+            url: DEFAULT_URL,
             text: self.as_ref(),
             source: Language::Detect,
             target: language,
+            client: None,
+            format: None,
+            translator: None,
         }
     }
 
-    fn from_lang(&self, language: Language) -> Query {
+    fn from_lang(&self, language: Language) -> Query<'_> {
         Query {
-            url: "https://libretranslate.com/",
+            url: DEFAULT_URL,
             text: self.as_ref(),
             source: language,
             target: Language::default(),
+            client: None,
+            format: None,
+            translator: None,
         }
     }
 }
 
 /// Build Translations more verbosely.
-#[derive(Debug, Clone, PartialEq, Hash)]
-pub struct TranslationBuilder {
+#[derive(Debug, Clone)]
+pub struct TranslationBuilder<'a> {
     pub url: String,
     pub source: Language,
     pub target: Language,
     pub input: String,
     key: Option<String>,
+    client: Option<&'a Client>,
+    translator: Option<&'a dyn Translator>,
+    chunked: Option<bool>,
+    char_limit: Option<usize>,
+    format: Option<Format>,
+    alternatives: Option<usize>,
+    retry: Option<RetryPolicy>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
 }
 
-impl TranslationBuilder {
+impl<'a> TranslationBuilder<'a> {
     pub fn new() -> Self {
         Self {
-            url: String::from("https://libretranslate.com/"),
+            url: String::from(DEFAULT_URL),
             source: Language::Detect,
             target: Language::default(),
             input: String::new(),
             key: None,
+            client: None,
+            translator: None,
+            chunked: None,
+            char_limit: None,
+            format: None,
+            alternatives: None,
+            retry: None,
+            timeout: None,
+            connect_timeout: None,
         }
     }
 
@@ -528,39 +601,143 @@ impl TranslationBuilder {
         self
     }
 
-    pub async fn translate(mut self) -> Result<Translation, TranslateError> {
-        if self.input.is_empty() {
-            return Ok(Translation {
-                url: self.url,
-                source: self.source,
-                target: self.target,
-                input: self.input,
-                output: String::new(),
-            });
+    /// Split the text if it's longer than the character limit instead of failing, see [`Client::chunked`](Client::chunked).
+    pub fn chunked(mut self, chunked: bool) -> Self {
+        self.chunked = Some(chunked);
+        self
+    }
+
+    /// Set the number of characters the instance accepts in a single request, see [`Client::char_limit`](Client::char_limit).
+    pub fn char_limit(mut self, limit: usize) -> Self {
+        self.char_limit = Some(limit);
+        self
+    }
+
+    /// Set the format of the text, see [`Format`](Format).
+    pub fn format(mut self, format: Format) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Ask for up to `count` alternative translations, see [`Client::alternatives`](Client::alternatives).
+    pub fn alternatives(mut self, count: usize) -> Self {
+        self.alternatives = Some(count);
+        self
+    }
+
+    /// Retry the translation if it fails with a transient error, see [`Client::retry`](Client::retry).
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+
+    /// Give up on a request after `timeout`, see [`Client::timeout`](Client::timeout).
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Give up on connecting after `timeout`, see [`Client::connect_timeout`](Client::connect_timeout).
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Send the translation through a [`Client`](Client). The builder's URL and key are then ignored.
+    pub fn client(mut self, client: &'a Client) -> Self {
+        self.url = client.url().to_string();
+        self.client = Some(client);
+        self.translator = None;
+        self
+    }
+
+    /// Translate with any [`Translator`](Translator). The builder's URL, key and request options are then ignored.
+    pub fn translator(mut self, translator: &'a dyn Translator) -> Self {
+        self.translator = Some(translator);
+        self.client = None;
+        self
+    }
+
+    pub async fn translate(self) -> Result<Translation, TranslateError> {
+        match self.translator {
+            Some(translator) => self.translate_with(translator).await,
+            None => {
+                let client = self.prepared_client();
+
+                self.translate_with(&client).await
+            }
+        }
+    }
+
+    /// The builder's client, or one for its URL and key, with the builder's options applied.
+    pub(crate) fn prepared_client(&self) -> Client {
+        let mut client = match self.client {
+            Some(client) => client.clone(),
+            None => {
+                let client = Client::new(&self.url);
+
+                match &self.key {
+                    Some(key) => client.key(key),
+                    None => client,
+                }
+            }
         };
 
-        let data = translate_url(
-            self.source,
-            self.target,
-            self.input.clone(),
-            self.url.clone(),
-            self.key,
-        )
-        .await?;
+        if let Some(chunked) = self.chunked {
+            client = client.chunked(chunked);
+        }
+
+        if let Some(limit) = self.char_limit {
+            client = client.char_limit(limit);
+        }
+
+        if let Some(format) = self.format {
+            client = client.format(format);
+        }
+
+        if let Some(count) = self.alternatives {
+            client = client.alternatives(count);
+        }
+
+        if let Some(policy) = &self.retry {
+            client = client.retry(policy.clone());
+        }
+
+        if let Some(timeout) = self.timeout {
+            client = client.timeout(timeout);
+        }
+
+        if let Some(timeout) = self.connect_timeout {
+            client = client.connect_timeout(timeout);
+        }
+
+        client
+    }
+
+    pub(crate) async fn translate_with(
+        mut self,
+        translator: &dyn Translator,
+    ) -> Result<Translation, TranslateError> {
+        let data = translator
+            .translate(self.source, self.target, &self.input)
+            .await?;
 
         self.source = data.source;
         self.target = data.target;
 
         Ok(Translation {
-            url: self.url,
+            url: data.url,
             source: self.source,
             target: self.target,
             input: self.input,
             output: data.output,
+            confidence: data.confidence,
+            alternatives: data.alternatives,
         })
     }
 }
-impl Default for TranslationBuilder {
+
+impl Default for TranslationBuilder<'_> {
     fn default() -> Self {
         Self::new()
     }
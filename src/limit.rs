@@ -0,0 +1,219 @@
+use futures_timer::Delay;
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// How fast a [`Client`](crate::Client) may send requests to each instance, see [`Client::rate_limit`](crate::Client::rate_limit).
+///
+/// Requests over the limit wait for their turn instead of failing.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RateLimit {
+    requests_per_second: Option<f64>,
+    chars_per_minute: Option<u32>,
+}
+
+impl RateLimit {
+    /// No limit, until one is set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Send at most `requests` requests per second, on average. Can be less than 1.
+    pub fn requests_per_second(mut self, requests: f64) -> Self {
+        self.requests_per_second = Some(requests).filter(|requests| *requests > 0.0);
+        self
+    }
+
+    /// Send at most `chars` characters of text per minute.
+    pub fn chars_per_minute(mut self, chars: u32) -> Self {
+        self.chars_per_minute = Some(chars).filter(|chars| *chars > 0);
+        self
+    }
+}
+
+/// Token buckets enforcing a [`RateLimit`](RateLimit) on each instance, shared by the clones of a client.
+#[derive(Debug)]
+pub(crate) struct Limiter {
+    limit: RateLimit,
+    /// The request and character buckets of each instance, by URL.
+    instances: Mutex<HashMap<String, [Option<Bucket>; 2]>>,
+}
+
+impl Limiter {
+    pub fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            instances: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn buckets(&self) -> [Option<Bucket>; 2] {
+        let requests = self
+            .limit
+            .requests_per_second
+            .map(|rate| Bucket::new(rate.max(1.0), rate));
+        let chars = self
+            .limit
+            .chars_per_minute
+            .map(|chars| Bucket::new(chars as f64, chars as f64 / 60.0));
+
+        [requests, chars]
+    }
+
+    /// Wait until a request with `chars` characters of text can be sent to `instance`.
+    pub async fn acquire(&self, instance: &str, chars: usize) {
+        let amounts = [1.0, chars as f64];
+
+        loop {
+            let wait = {
+                let mut instances = self
+                    .instances
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner);
+                let buckets = instances
+                    .entry(instance.to_string())
+                    .or_insert_with(|| self.buckets());
+                let now = Instant::now();
+
+                let wait = buckets
+                    .iter_mut()
+                    .zip(amounts)
+                    .filter_map(|(bucket, amount)| {
+                        let bucket = bucket.as_mut()?;
+                        bucket.refill(now);
+                        Some(bucket.wait(amount))
+                    })
+                    .max()
+                    .unwrap_or_default();
+
+                if wait.is_zero() {
+                    for (bucket, amount) in buckets.iter_mut().zip(amounts) {
+                        if let Some(bucket) = bucket {
+                            bucket.tokens -= amount;
+                        }
+                    }
+                }
+
+                wait
+            };
+
+            if wait.is_zero() {
+                return;
+            }
+
+            Delay::new(wait).await;
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    capacity: f64,
+    /// Tokens added per second.
+    rate: f64,
+    /// Goes below zero after taking more than the capacity at once.
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    fn new(capacity: f64, rate: f64) -> Self {
+        Self {
+            capacity,
+            rate,
+            tokens: capacity,
+            updated: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.updated).as_secs_f64();
+
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        self.updated = now;
+    }
+
+    /// How long until `amount` tokens can be taken. More than the capacity can be taken once the bucket is full.
+    fn wait(&self, amount: f64) -> Duration {
+        let missing = amount.min(self.capacity) - self.tokens;
+
+        if missing <= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(missing / self.rate)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// How long acquiring `chars` takes on `instance`.
+    async fn time(limiter: &Limiter, instance: &str, chars: usize) -> Duration {
+        let start = Instant::now();
+        limiter.acquire(instance, chars).await;
+        start.elapsed()
+    }
+
+    #[test]
+    fn bucket_wait() {
+        let mut bucket = Bucket::new(10.0, 5.0);
+
+        assert_eq!(bucket.wait(10.0), Duration::ZERO);
+
+        bucket.tokens = 0.0;
+        assert_eq!(bucket.wait(5.0), Duration::from_secs(1));
+        // Never more than a full bucket.
+        assert_eq!(bucket.wait(100.0), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn bucket_refill() {
+        let mut bucket = Bucket::new(10.0, 5.0);
+        bucket.tokens = 0.0;
+
+        bucket.refill(bucket.updated + Duration::from_secs(1));
+        assert_eq!(bucket.tokens, 5.0);
+
+        bucket.refill(bucket.updated + Duration::from_secs(10));
+        assert_eq!(bucket.tokens, 10.0);
+    }
+
+    #[tokio::test]
+    async fn requests_per_second() {
+        let limiter = Limiter::new(RateLimit::new().requests_per_second(20.0));
+
+        for _ in 0..20 {
+            assert!(time(&limiter, "a", 0).await < Duration::from_millis(10));
+        }
+
+        assert!(time(&limiter, "a", 0).await >= Duration::from_millis(40));
+    }
+
+    #[tokio::test]
+    async fn chars_per_minute() {
+        let limiter = Limiter::new(RateLimit::new().chars_per_minute(6000));
+
+        assert!(time(&limiter, "a", 6000).await < Duration::from_millis(10));
+        assert!(time(&limiter, "a", 10).await >= Duration::from_millis(90));
+    }
+
+    #[tokio::test]
+    async fn instances_are_limited_separately() {
+        let limiter = Limiter::new(RateLimit::new().requests_per_second(1.0));
+
+        assert!(time(&limiter, "a", 0).await < Duration::from_millis(10));
+        assert!(time(&limiter, "b", 0).await < Duration::from_millis(10));
+        assert!(time(&limiter, "a", 0).await >= Duration::from_millis(900));
+    }
+
+    #[tokio::test]
+    async fn no_limit() {
+        let limiter = Limiter::new(RateLimit::new());
+
+        for _ in 0..100 {
+            assert!(time(&limiter, "a", 5000).await < Duration::from_millis(10));
+        }
+    }
+}
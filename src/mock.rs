@@ -0,0 +1,791 @@
+//! A LibreTranslate instance running in the current process, to test code that translates without a network.
+//!
+//! [`MockServer`](MockServer) answers `/translate`, `/detect`, `/languages`, `/frontend/settings`, `/suggest`
+//! and `/translate_file` like a real instance would. By default a text is translated to itself prefixed with
+//! the target code (ex. "[fr] Hello"), every [`Language`](Language) is served and there is no API key.
+//! Texts in the HTML format are parsed as documents of their own first, like an instance does.
+//! Everything else is scripted on the server while it runs. It is enabled by the `mock` feature.
+//!
+//! ```rust
+//! use libretranslate::mock::MockServer;
+//! use libretranslate::{Language, TranslationBuilder};
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let server = MockServer::start();
+//!     server.translation("Hello world!", "Bonjour le monde !");
+//!
+//!     let data = TranslationBuilder::new()
+//!         .url(server.url())
+//!         .from_lang(Language::English)
+//!         .to_lang(Language::French)
+//!         .text("Hello world!")
+//!         .translate()
+//!         .await
+//!         .unwrap();
+//!
+//!     assert_eq!(data.output, "Bonjour le monde !");
+//! }
+//! ```
+
+use crate::chunk::{self, Tag};
+use crate::{client, Language};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::{HashMap, VecDeque};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// The confidence of every detection made by the server.
+const CONFIDENCE: f32 = 90.0;
+
+/// A LibreTranslate instance listening on a local port until it is dropped.
+#[derive(Debug)]
+pub struct MockServer {
+    url: String,
+    shared: Arc<Shared>,
+    thread: Option<JoinHandle<()>>,
+}
+
+/// A request received by a [`MockServer`](MockServer).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockRequest {
+    pub method: String,
+    /// The path without the query string (ex. "/translate").
+    pub path: String,
+    pub body: Vec<u8>,
+}
+
+impl MockServer {
+    /// Start a server on a free local port.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no local port can be bound.
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Unable to bind a local port");
+        let addr = listener
+            .local_addr()
+            .expect("Unable to read the bound port");
+        let url = format!("http://{}/", addr);
+
+        // The port may have been used by a server that is gone, along with what was cached about it.
+        client::forget(&url);
+
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State::new(url.clone())),
+            stopped: AtomicBool::new(false),
+        });
+
+        let thread = {
+            let shared = Arc::clone(&shared);
+
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if shared.stopped.load(Ordering::SeqCst) {
+                        break;
+                    }
+
+                    if let Ok(stream) = stream {
+                        let shared = Arc::clone(&shared);
+                        thread::spawn(move || shared.handle(stream));
+                    }
+                }
+            })
+        };
+
+        Self {
+            url,
+            shared,
+            thread: Some(thread),
+        }
+    }
+
+    /// The base URL of the server (ex. "http://127.0.0.1:40123/").
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Translate `input` to `output`, whatever the languages.
+    pub fn translation<T: AsRef<str>, U: AsRef<str>>(&self, input: T, output: U) -> &Self {
+        self.state()
+            .translations
+            .insert(input.as_ref().to_string(), output.as_ref().to_string());
+        self
+    }
+
+    /// Detect every text as `language`. Defaults to [`Language::English`](Language::English).
+    pub fn detect_as(&self, language: Language) -> &Self {
+        self.state().detected = language;
+        self
+    }
+
+    /// Only serve these languages, each of them translatable to every other one.
+    pub fn languages<I: IntoIterator<Item = Language>>(&self, languages: I) -> &Self {
+        self.state().languages = languages.into_iter().collect();
+        self
+    }
+
+    /// Refuse texts longer than `limit` characters, `None` for no limit. Defaults to 5000.
+    pub fn char_limit(&self, limit: Option<usize>) -> &Self {
+        self.state().char_limit = limit;
+        self
+    }
+
+    /// Refuse requests that don't come with `key`.
+    pub fn key<T: AsRef<str>>(&self, key: T) -> &Self {
+        self.state().key = Some(key.as_ref().to_string());
+        self
+    }
+
+    /// Accept or refuse suggestions. Accepted by default.
+    pub fn suggestions(&self, enabled: bool) -> &Self {
+        self.state().suggestions = enabled;
+        self
+    }
+
+    /// Accept or refuse files. Only ".txt" files are accepted by default.
+    pub fn files(&self, enabled: bool) -> &Self {
+        self.state().files = enabled;
+        self
+    }
+
+    /// Answer the next request to `path` (ex. "/translate") with `status` and `{"error": message}`.
+    ///
+    /// Call it again to fail more requests, they fail in the order they were scripted.
+    pub fn fail<T: AsRef<str>, U: AsRef<str>>(&self, path: T, status: u16, message: U) -> &Self {
+        self.state()
+            .failures
+            .entry(path.as_ref().to_string())
+            .or_default()
+            .push_back((status, message.as_ref().to_string()));
+        self
+    }
+
+    /// Wait `latency` before answering each request.
+    pub fn latency(&self, latency: Duration) -> &Self {
+        self.state().latency = latency;
+        self
+    }
+
+    /// Answer with 429 Too Many Requests once `requests` requests were made in the last `per`.
+    pub fn rate_limit(&self, requests: usize, per: Duration) -> &Self {
+        self.state().rate_limit = Some(RateLimit {
+            requests,
+            per,
+            received: VecDeque::new(),
+        });
+        self
+    }
+
+    /// The requests received so far, in order.
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.state().requests.clone()
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.shared.state()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.shared.stopped.store(true, Ordering::SeqCst);
+
+        // Wake the listening thread up so it sees it was stopped.
+        let _ = TcpStream::connect(self.url.trim_start_matches("http://").trim_end_matches('/'));
+
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Shared {
+    state: Mutex<State>,
+    stopped: AtomicBool,
+}
+
+#[derive(Debug)]
+struct State {
+    url: String,
+    translations: HashMap<String, String>,
+    detected: Language,
+    languages: Vec<Language>,
+    char_limit: Option<usize>,
+    key: Option<String>,
+    suggestions: bool,
+    files: bool,
+    failures: HashMap<String, VecDeque<(u16, String)>>,
+    latency: Duration,
+    rate_limit: Option<RateLimit>,
+    requests: Vec<MockRequest>,
+    /// Translated files, downloaded by their index.
+    downloads: Vec<Vec<u8>>,
+}
+
+#[derive(Debug)]
+struct RateLimit {
+    requests: usize,
+    per: Duration,
+    received: VecDeque<Instant>,
+}
+
+/// What the server answers, a status with a JSON body.
+struct Reply {
+    status: u16,
+    body: Vec<u8>,
+    retry_after: Option<u64>,
+}
+
+impl Reply {
+    fn json(body: serde_json::Value) -> Self {
+        Self {
+            status: 200,
+            body: body.to_string().into_bytes(),
+            retry_after: None,
+        }
+    }
+
+    fn error<T: AsRef<str>>(status: u16, message: T) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message.as_ref() })
+                .to_string()
+                .into_bytes(),
+            retry_after: None,
+        }
+    }
+}
+
+/// Body of `POST /translate`.
+#[derive(Deserialize)]
+struct TranslateBody {
+    q: Texts,
+    source: String,
+    target: String,
+    #[serde(default)]
+    alternatives: usize,
+    #[serde(default)]
+    format: String,
+    api_key: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Texts {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// Body of `POST /detect`.
+#[derive(Deserialize)]
+struct DetectBody {
+    q: String,
+    api_key: Option<String>,
+}
+
+/// Body of `POST /suggest`.
+#[derive(Deserialize)]
+struct SuggestBody {
+    source: String,
+    target: String,
+    api_key: Option<String>,
+}
+
+impl Shared {
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn handle(&self, mut stream: TcpStream) {
+        let request = match read_request(&mut stream) {
+            Some(request) => request,
+            None => return,
+        };
+
+        let latency = {
+            let mut state = self.state();
+            state.requests.push(request.clone());
+            state.latency
+        };
+
+        thread::sleep(latency);
+
+        let reply = self.state().reply(&request);
+
+        let _ = write_reply(&mut stream, reply);
+    }
+}
+
+impl State {
+    fn new(url: String) -> Self {
+        Self {
+            url,
+            translations: HashMap::new(),
+            detected: Language::English,
+            languages: Language::all().to_vec(),
+            char_limit: Some(5000),
+            key: None,
+            suggestions: true,
+            files: true,
+            failures: HashMap::new(),
+            latency: Duration::ZERO,
+            rate_limit: None,
+            requests: Vec::new(),
+            downloads: Vec::new(),
+        }
+    }
+
+    fn reply(&mut self, request: &MockRequest) -> Reply {
+        if let Some(limit) = &mut self.rate_limit {
+            let now = Instant::now();
+
+            while let Some(first) = limit.received.front() {
+                if now.duration_since(*first) < limit.per {
+                    break;
+                }
+
+                limit.received.pop_front();
+            }
+
+            if limit.received.len() >= limit.requests {
+                let wait = limit.per - now.duration_since(limit.received[0]);
+
+                return Reply {
+                    retry_after: Some(wait.as_secs_f64().ceil() as u64),
+                    ..Reply::error(429, "Too many request limits violations")
+                };
+            }
+
+            limit.received.push_back(now);
+        }
+
+        if let Some((status, message)) = self
+            .failures
+            .get_mut(&request.path)
+            .and_then(VecDeque::pop_front)
+        {
+            return Reply::error(status, message);
+        }
+
+        let result = match (request.method.as_str(), request.path.as_str()) {
+            ("POST", "/translate") => self.translate(&request.body),
+            ("POST", "/detect") => self.detect(&request.body),
+            ("GET", "/languages") => Ok(self.languages()),
+            ("GET", "/frontend/settings") => Ok(self.settings()),
+            ("POST", "/suggest") => self.suggest(&request.body),
+            ("POST", "/translate_file") => self.translate_file(&request.body),
+            ("GET", path) if path.starts_with("/download_file/") => self.download(path),
+            _ => Err(Reply::error(404, "Not Found")),
+        };
+
+        result.unwrap_or_else(|reply| reply)
+    }
+
+    fn translate(&self, body: &[u8]) -> Result<Reply, Reply> {
+        let body: TranslateBody = parse(body)?;
+
+        self.check_key(body.api_key.as_deref())?;
+        let target = self.check_language(&body.target)?;
+
+        let source = match body.source.as_str() {
+            "auto" => None,
+            source => Some(self.check_language(source)?),
+        };
+
+        let texts = match &body.q {
+            Texts::One(text) => vec![text.as_str()],
+            Texts::Many(texts) => texts.iter().map(String::as_str).collect(),
+        };
+
+        self.check_length(texts.iter().map(|text| text.chars().count()).sum())?;
+
+        let outputs: Vec<String> = texts
+            .iter()
+            .map(|text| match body.format.as_str() {
+                "html" => self.translation(&balance(text), target),
+                _ => self.translation(text, target),
+            })
+            .collect();
+
+        let alternatives: Vec<Vec<String>> = outputs
+            .iter()
+            .map(|output| {
+                (1..=body.alternatives)
+                    .map(|i| format!("{} ({})", output, i))
+                    .collect()
+            })
+            .collect();
+
+        let detected = json!({ "language": self.detected.as_code(), "confidence": CONFIDENCE });
+
+        let mut res = match body.q {
+            Texts::One(_) => json!({
+                "translatedText": outputs[0],
+                "alternatives": alternatives[0],
+            }),
+            Texts::Many(_) => json!({
+                "translatedText": outputs,
+                "alternatives": alternatives,
+            }),
+        };
+
+        if source.is_none() {
+            res["detectedLanguage"] = match body.q {
+                Texts::One(_) => detected,
+                Texts::Many(_) => json!(vec![detected; outputs.len()]),
+            };
+        }
+
+        Ok(Reply::json(res))
+    }
+
+    fn detect(&self, body: &[u8]) -> Result<Reply, Reply> {
+        let body: DetectBody = parse(body)?;
+
+        self.check_key(body.api_key.as_deref())?;
+        self.check_length(body.q.chars().count())?;
+
+        Ok(Reply::json(json!([
+            { "language": self.detected.as_code(), "confidence": CONFIDENCE }
+        ])))
+    }
+
+    fn languages(&self) -> Reply {
+        let codes: Vec<&str> = self.languages.iter().map(Language::as_code).collect();
+
+        let languages: Vec<serde_json::Value> = self
+            .languages
+            .iter()
+            .map(|language| {
+                json!({
+                    "code": language.as_code(),
+                    "name": language.as_pretty(),
+                    "targets": codes,
+                })
+            })
+            .collect();
+
+        Reply::json(json!(languages))
+    }
+
+    fn settings(&self) -> Reply {
+        let char_limit = match self.char_limit {
+            Some(limit) => limit as i64,
+            None => -1,
+        };
+
+        Reply::json(json!({
+            "charLimit": char_limit,
+            "keyRequired": self.key.is_some(),
+            "apiKeys": self.key.is_some(),
+            "suggestions": self.suggestions,
+            "filesTranslation": self.files,
+            "supportedFilesFormat": [".txt"],
+        }))
+    }
+
+    fn suggest(&self, body: &[u8]) -> Result<Reply, Reply> {
+        let body: SuggestBody = parse(body)?;
+
+        self.check_key(body.api_key.as_deref())?;
+
+        if !self.suggestions {
+            return Err(Reply::error(
+                403,
+                "Suggestions are disabled on this server.",
+            ));
+        }
+
+        self.check_language(&body.source)?;
+        self.check_language(&body.target)?;
+
+        Ok(Reply::json(json!({ "success": true })))
+    }
+
+    fn translate_file(&mut self, body: &[u8]) -> Result<Reply, Reply> {
+        let form = parse_multipart(body).ok_or_else(|| Reply::error(400, "Invalid request"))?;
+
+        let field = |name: &str| {
+            form.iter()
+                .find(|part| part.name == name)
+                .map(|part| String::from_utf8_lossy(&part.content).into_owned())
+        };
+
+        self.check_key(field("api_key").as_deref())?;
+
+        if !self.files {
+            return Err(Reply::error(
+                403,
+                "Files translation are disabled on this server.",
+            ));
+        }
+
+        let source = field("source").unwrap_or_default();
+        let target = field("target").unwrap_or_default();
+
+        if source != "auto" {
+            self.check_language(&source)?;
+        }
+
+        let target = self.check_language(&target)?;
+
+        let file = form
+            .iter()
+            .find(|part| part.name == "file")
+            .ok_or_else(|| Reply::error(400, "Invalid request: missing file parameter"))?;
+
+        if !file.filename.to_lowercase().ends_with(".txt") {
+            return Err(Reply::error(
+                400,
+                "Invalid request: file format not supported",
+            ));
+        }
+
+        let text = String::from_utf8_lossy(&file.content);
+        self.check_length(text.chars().count())?;
+
+        let translated = self.translation(&text, target).into_bytes();
+        self.downloads.push(translated);
+
+        Ok(Reply::json(json!({
+            "translatedFileUrl": format!("{}download_file/{}", self.url, self.downloads.len() - 1),
+        })))
+    }
+
+    fn download(&self, path: &str) -> Result<Reply, Reply> {
+        let bytes = path
+            .trim_start_matches("/download_file/")
+            .parse::<usize>()
+            .ok()
+            .and_then(|index| self.downloads.get(index))
+            .ok_or_else(|| Reply::error(404, "Not Found"))?;
+
+        Ok(Reply {
+            status: 200,
+            body: bytes.clone(),
+            retry_after: None,
+        })
+    }
+
+    fn translation(&self, input: &str, target: Language) -> String {
+        match self.translations.get(input) {
+            Some(output) => output.clone(),
+            None => format!("[{}] {}", target.as_code(), input),
+        }
+    }
+
+    fn check_key(&self, key: Option<&str>) -> Result<(), Reply> {
+        match &self.key {
+            Some(expected) if key != Some(expected.as_str()) => Err(Reply::error(
+                403,
+                "Invalid API key. Visit the instance to get an API key.",
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_language(&self, code: &str) -> Result<Language, Reply> {
+        self.languages
+            .iter()
+            .find(|language| language.as_code() == code)
+            .copied()
+            .ok_or_else(|| Reply::error(400, format!("{} is not supported", code)))
+    }
+
+    fn check_length(&self, chars: usize) -> Result<(), Reply> {
+        match self.char_limit {
+            Some(limit) if chars > limit => Err(Reply::error(
+                400,
+                format!(
+                    "Invalid request: request ({}) exceeds text limit ({})",
+                    chars, limit
+                ),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Parse `html` as a document of its own like an instance does, closing the elements left open and dropping the
+/// closing tags of elements that aren't.
+fn balance(html: &str) -> String {
+    let mut output = String::with_capacity(html.len());
+    let mut open: Vec<String> = Vec::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        let end = match rest[start..].find('>') {
+            Some(end) => start + end + 1,
+            None => break,
+        };
+
+        output.push_str(&rest[..start]);
+
+        match chunk::tag(&rest[start..end]) {
+            Tag::Open(name) => {
+                open.push(name);
+                output.push_str(&rest[start..end]);
+            }
+            Tag::Close(name) => {
+                if let Some(index) = open.iter().rposition(|open| *open == name) {
+                    for name in open.drain(index..).skip(1).rev() {
+                        output.push_str(&format!("</{}>", name));
+                    }
+
+                    output.push_str(&rest[start..end]);
+                }
+            }
+            Tag::Empty => output.push_str(&rest[start..end]),
+        }
+
+        rest = &rest[end..];
+    }
+
+    output.push_str(rest);
+
+    for name in open.iter().rev() {
+        output.push_str(&format!("</{}>", name));
+    }
+
+    output
+}
+
+fn parse<'a, T: Deserialize<'a>>(body: &'a [u8]) -> Result<T, Reply> {
+    serde_json::from_slice(body)
+        .map_err(|error| Reply::error(400, format!("Invalid request: {}", error)))
+}
+
+fn read_request(stream: &mut TcpStream) -> Option<MockRequest> {
+    let mut reader = BufReader::new(stream);
+
+    let mut line = String::new();
+    reader.read_line(&mut line).ok()?;
+
+    let mut parts = line.split_whitespace();
+    let method = parts.next()?.to_string();
+    let target = parts.next()?;
+    let path = target.split('?').next().unwrap_or(target).to_string();
+
+    let mut length = 0;
+
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header).ok()?;
+
+        let header = header.trim_end();
+
+        if header.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse().ok()?;
+            }
+        }
+    }
+
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).ok()?;
+
+    Some(MockRequest { method, path, body })
+}
+
+fn write_reply(stream: &mut TcpStream, reply: Reply) -> std::io::Result<()> {
+    let content_type = match reply.body.first() {
+        Some(b'{') | Some(b'[') => "application/json",
+        _ => "application/octet-stream",
+    };
+
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n",
+        reply.status,
+        reason(reply.status),
+        content_type,
+        reply.body.len()
+    );
+
+    if let Some(seconds) = reply.retry_after {
+        head.push_str(&format!("Retry-After: {}\r\n", seconds));
+    }
+
+    head.push_str("\r\n");
+
+    stream.write_all(head.as_bytes())?;
+    stream.write_all(&reply.body)?;
+    stream.flush()
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        413 => "Payload Too Large",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        _ => "Unknown",
+    }
+}
+
+/// A part of a `multipart/form-data` body.
+struct Part {
+    name: String,
+    filename: String,
+    content: Vec<u8>,
+}
+
+fn parse_multipart(body: &[u8]) -> Option<Vec<Part>> {
+    // The body starts with the boundary, no need to read it from the headers.
+    let end = find(body, b"\r\n")?;
+    let boundary = [b"\r\n", &body[..end]].concat();
+
+    let mut parts = Vec::new();
+    let mut rest = &body[end + 2..];
+
+    while let Some(split) = find(rest, &boundary) {
+        let part = &rest[..split];
+        rest = &rest[split + boundary.len()..];
+
+        let head_end = find(part, b"\r\n\r\n")?;
+        let head = String::from_utf8_lossy(&part[..head_end]);
+
+        let attribute = |key: &str| {
+            let start = head.find(&format!("{}=\"", key))? + key.len() + 2;
+            let end = head[start..].find('"')? + start;
+            Some(head[start..end].to_string())
+        };
+
+        parts.push(Part {
+            name: attribute("name")?,
+            filename: attribute("filename").unwrap_or_default(),
+            content: part[head_end + 4..].to_vec(),
+        });
+
+        // The last boundary is followed by "--".
+        if rest.starts_with(b"--") {
+            break;
+        }
+
+        rest = rest.strip_prefix(b"\r\n")?;
+    }
+
+    Some(parts)
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
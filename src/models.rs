@@ -0,0 +1,162 @@
+use crate::{Detection, InstanceInfo, TranslateError};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// Body of `POST /translate`, `Q` is either a single text or a slice of them.
+#[derive(Debug, Serialize)]
+pub(crate) struct TranslateRequest<'a, Q> {
+    pub q: Q,
+    pub source: &'a str,
+    pub target: &'a str,
+    pub format: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alternatives: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<&'a str>,
+}
+
+/// Response of `POST /translate` for a single text.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TranslateResponse {
+    pub translated_text: String,
+    /// Only present when the source language was detected.
+    pub detected_language: Option<Detection>,
+    #[serde(default)]
+    pub alternatives: Vec<String>,
+}
+
+/// Response of `POST /translate` for many texts.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BatchTranslateResponse {
+    pub translated_text: Vec<String>,
+    /// Only present when the source language was detected.
+    #[serde(default)]
+    pub detected_language: Vec<Detection>,
+    #[serde(default)]
+    pub alternatives: Vec<Vec<String>>,
+}
+
+/// Body of `POST /detect`.
+#[derive(Debug, Serialize)]
+pub(crate) struct DetectRequest<'a> {
+    pub q: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<&'a str>,
+}
+
+/// Body of `POST /suggest`.
+#[derive(Debug, Serialize)]
+pub(crate) struct SuggestRequest<'a> {
+    pub q: &'a str,
+    pub s: &'a str,
+    pub source: &'a str,
+    pub target: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<&'a str>,
+}
+
+/// Response of `POST /suggest`.
+#[derive(Debug, Deserialize)]
+pub(crate) struct SuggestResponse {
+    pub success: bool,
+}
+
+/// Form fields of `POST /translate_file`, sent alongside the file itself.
+#[derive(Debug)]
+pub(crate) struct TranslateFileRequest<'a> {
+    pub source: &'a str,
+    pub target: &'a str,
+    pub api_key: Option<&'a str>,
+}
+
+impl<'a> TranslateFileRequest<'a> {
+    pub fn fields(&self) -> Vec<(&'static str, &'a str)> {
+        let mut fields = vec![("source", self.source), ("target", self.target)];
+
+        if let Some(key) = self.api_key {
+            fields.push(("api_key", key));
+        }
+
+        fields
+    }
+}
+
+/// Response of `POST /translate_file`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TranslateFileResponse {
+    pub translated_file_url: String,
+}
+
+/// Response of `GET /frontend/settings`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SettingsResponse {
+    /// Negative when there is no limit, missing on instances older than the setting.
+    pub char_limit: Option<i64>,
+    #[serde(default)]
+    pub key_required: bool,
+    #[serde(default)]
+    pub api_keys: bool,
+    #[serde(default)]
+    pub suggestions: bool,
+    #[serde(default = "default_files_translation")]
+    pub files_translation: bool,
+    #[serde(default)]
+    pub supported_files_format: Vec<String>,
+}
+
+fn default_files_translation() -> bool {
+    true
+}
+
+impl SettingsResponse {
+    pub fn into_info(self, default_char_limit: usize) -> InstanceInfo {
+        let char_limit = match self.char_limit {
+            Some(limit) if limit > 0 => Some(limit as usize),
+            Some(_) => None,
+            None => Some(default_char_limit),
+        };
+
+        InstanceInfo {
+            char_limit,
+            key_required: self.key_required,
+            api_keys: self.api_keys,
+            suggestions: self.suggestions,
+            files_translation: self.files_translation,
+            supported_files_format: self.supported_files_format,
+        }
+    }
+}
+
+/// Body of every response the instance refused.
+#[derive(Debug, Deserialize)]
+pub(crate) struct ErrorResponse {
+    pub error: String,
+}
+
+/// Decode a response body, turning an error status or an [`ErrorResponse`](ErrorResponse) into an error.
+pub(crate) fn decode<T: DeserializeOwned>(
+    status: u16,
+    retry_after: Option<&str>,
+    body: &str,
+) -> Result<T, TranslateError> {
+    let message = match serde_json::from_str(body) {
+        Ok(ErrorResponse { error }) => Some(error),
+        Err(_) => None,
+    };
+
+    if !(200..300).contains(&status) {
+        let message = message.unwrap_or_else(|| body.trim().to_string());
+        return Err(TranslateError::from_response(status, retry_after, message));
+    }
+
+    // Some instances answer errors with a success status.
+    if let Some(message) = message {
+        return Err(TranslateError::from_response(400, retry_after, message));
+    }
+
+    serde_json::from_str(body).map_err(TranslateError::decode)
+}
@@ -0,0 +1,323 @@
+use crate::{Detection, Language, LanguageInfo, TranslateError, Translation, Translator};
+use futures_util::future::{self, BoxFuture};
+
+/// Where pseudo-localized translations come from.
+pub(crate) const URL: &str = "pseudo://localization/";
+
+/// The language every text is detected as, with a confidence of 100.
+const DETECTED: Language = Language::English;
+
+/// Marks the paragraph as right-to-left.
+const RIGHT_TO_LEFT_MARK: char = '\u{200F}';
+/// Displays the text after it right-to-left, until [`POP`](POP).
+const RIGHT_TO_LEFT_OVERRIDE: char = '\u{202E}';
+const POP: char = '\u{202C}';
+
+/// Fake translations for testing user interfaces, see [`Client::pseudo`](crate::Client::pseudo).
+///
+/// A text is pseudo-localized by accenting its letters, padding it with `~` and wrapping it in brackets
+/// (ex. "This is text" becomes "[Ŧĥîš îš ţéxţ ~~~]"). It stays readable, while truncated text, hard-coded
+/// strings and fonts missing accents stand out.
+///
+/// Placeholders are kept as is: `{name}`, `${name}`, printf specifiers like `%s` or `%1$d`, HTML tags and
+/// entities. Texts translated to a right-to-left language (Arabic, Hebrew, Persian and Urdu) are displayed
+/// right-to-left.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pseudo {
+    expansion: f32,
+    rtl: bool,
+    placeholders: bool,
+}
+
+impl Pseudo {
+    /// Texts 30% longer, right-to-left targets simulated and placeholders kept.
+    pub fn new() -> Self {
+        Self {
+            expansion: 0.3,
+            rtl: true,
+            placeholders: true,
+        }
+    }
+
+    /// Make texts longer by `ratio` of their length (ex. 0.3 for 30%), as translations often are.
+    pub fn expansion(mut self, ratio: f32) -> Self {
+        self.expansion = ratio.max(0.0);
+        self
+    }
+
+    /// Whether texts translated to a right-to-left language are displayed right-to-left.
+    pub fn rtl(mut self, rtl: bool) -> Self {
+        self.rtl = rtl;
+        self
+    }
+
+    /// Whether placeholders are kept as is instead of being accented like the rest of the text.
+    pub fn placeholders(mut self, placeholders: bool) -> Self {
+        self.placeholders = placeholders;
+        self
+    }
+
+    /// Pseudo-localize `input` as if it was translated to `target`.
+    pub fn localize<T: AsRef<str>>(&self, input: T, target: Language) -> String {
+        let input = input.as_ref();
+        let rtl = self.rtl && is_rtl(target);
+
+        let mut output = String::with_capacity(input.len() * 2);
+        let mut chars = 0;
+        let mut rest = input;
+
+        if rtl {
+            output.push(RIGHT_TO_LEFT_MARK);
+        }
+
+        output.push('[');
+
+        while !rest.is_empty() {
+            let (text, placeholder) = if self.placeholders {
+                split_placeholder(rest)
+            } else {
+                (rest, "")
+            };
+
+            if !text.is_empty() {
+                chars += text.chars().count();
+
+                if rtl {
+                    output.push(RIGHT_TO_LEFT_OVERRIDE);
+                }
+
+                output.extend(text.chars().map(accent));
+
+                if rtl {
+                    output.push(POP);
+                }
+            }
+
+            output.push_str(placeholder);
+            rest = &rest[text.len() + placeholder.len()..];
+        }
+
+        let padding = (chars as f32 * self.expansion) as usize;
+
+        if padding > 0 {
+            output.push(' ');
+            output.push_str(&"~".repeat(padding));
+        }
+
+        output.push(']');
+        output
+    }
+}
+
+impl Default for Pseudo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Translates offline, like a client made with [`Client::pseudo`](crate::Client::pseudo).
+impl Translator for Pseudo {
+    fn translate<'a>(
+        &'a self,
+        source: Language,
+        target: Language,
+        input: &'a str,
+    ) -> BoxFuture<'a, Result<Translation, TranslateError>> {
+        let (source, confidence) = match source {
+            Language::Detect => (DETECTED, Some(100.0)),
+            source => (source, None),
+        };
+
+        Box::pin(future::ready(Ok(Translation {
+            url: format!("{}translate", URL),
+            source,
+            target,
+            input: input.to_string(),
+            output: match input {
+                "" => String::new(),
+                input => self.localize(input, target),
+            },
+            confidence,
+            alternatives: Vec::new(),
+        })))
+    }
+
+    fn translate_batch<'a>(
+        &'a self,
+        source: Language,
+        target: Language,
+        inputs: &'a [&'a str],
+    ) -> BoxFuture<'a, Vec<Result<Translation, TranslateError>>> {
+        Box::pin(future::join_all(
+            inputs
+                .iter()
+                .map(|input| self.translate(source, target, input)),
+        ))
+    }
+
+    fn detect<'a>(
+        &'a self,
+        _input: &'a str,
+    ) -> BoxFuture<'a, Result<Vec<Detection>, TranslateError>> {
+        Box::pin(future::ready(Ok(vec![Detection {
+            language: DETECTED,
+            confidence: 100.0,
+        }])))
+    }
+
+    fn languages(&self) -> BoxFuture<'_, Result<Vec<LanguageInfo>, TranslateError>> {
+        Box::pin(future::ready(Ok(languages())))
+    }
+}
+
+/// Every [`Language`](Language), each of them translatable to every other one.
+fn languages() -> Vec<LanguageInfo> {
+    let codes: Vec<String> = Language::all()
+        .iter()
+        .map(|language| language.as_code().to_string())
+        .collect();
+
+    Language::all()
+        .iter()
+        .map(|language| LanguageInfo {
+            code: language.as_code().to_string(),
+            name: language.as_pretty().to_string(),
+            targets: codes.clone(),
+        })
+        .collect()
+}
+
+fn is_rtl(language: Language) -> bool {
+    matches!(
+        language,
+        Language::Arabic | Language::Hebrew | Language::Persian | Language::Urdu
+    )
+}
+
+/// Split `text` into the text before its first placeholder and the placeholder itself, empty if there is none.
+fn split_placeholder(text: &str) -> (&str, &str) {
+    for (i, _) in text.char_indices() {
+        if let Some(len) = placeholder_len(&text[i..]) {
+            return (&text[..i], &text[i..i + len]);
+        }
+    }
+
+    (text, "")
+}
+
+/// The length in bytes of the placeholder `text` starts with, if it starts with one.
+fn placeholder_len(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+
+    match bytes.first()? {
+        b'{' => closing_brace(text),
+        b'$' if bytes.get(1) == Some(&b'{') => closing_brace(&text[1..]).map(|len| len + 1),
+        b'%' => {
+            if bytes.get(1) == Some(&b'%') {
+                return Some(2);
+            }
+
+            // Position, flags, width and precision, then the conversion (ex. "%1$-4.2f").
+            let len = 1 + bytes[1..]
+                .iter()
+                .take_while(|byte| byte.is_ascii_digit() || b"$-+#.".contains(byte))
+                .count();
+
+            bytes
+                .get(len)
+                .filter(|byte| byte.is_ascii_alphabetic() || **byte == b'@')
+                .map(|_| len + 1)
+        }
+        b'<' => {
+            let end = text[1..].find(['<', '>'])? + 1;
+            (bytes[end] == b'>' && end > 1).then_some(end + 1)
+        }
+        b'&' => {
+            let len = 1 + bytes[1..]
+                .iter()
+                .take_while(|byte| byte.is_ascii_alphanumeric() || **byte == b'#')
+                .count();
+
+            (len > 1 && bytes.get(len) == Some(&b';')).then_some(len + 1)
+        }
+        _ => None,
+    }
+}
+
+/// The length in bytes up to the brace closing the one `text` starts with, nested braces included.
+fn closing_brace(text: &str) -> Option<usize> {
+    let mut depth = 0;
+
+    for (i, byte) in text.bytes().enumerate() {
+        match byte {
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+fn accent(c: char) -> char {
+    match c {
+        'a' => 'á',
+        'b' => 'ƀ',
+        'c' => 'ç',
+        'd' => 'ð',
+        'e' => 'é',
+        'f' => 'ƒ',
+        'g' => 'ĝ',
+        'h' => 'ĥ',
+        'i' => 'î',
+        'j' => 'ĵ',
+        'k' => 'ķ',
+        'l' => 'ļ',
+        'm' => 'ɱ',
+        'n' => 'ñ',
+        'o' => 'ö',
+        'p' => 'þ',
+        'q' => 'ǫ',
+        'r' => 'ŕ',
+        's' => 'š',
+        't' => 'ţ',
+        'u' => 'û',
+        'v' => 'ṽ',
+        'w' => 'ŵ',
+        'y' => 'ý',
+        'z' => 'ž',
+        'A' => 'Å',
+        'B' => 'Ɓ',
+        'C' => 'Ç',
+        'D' => 'Ð',
+        'E' => 'É',
+        'F' => 'Ƒ',
+        'G' => 'Ĝ',
+        'H' => 'Ĥ',
+        'I' => 'Î',
+        'J' => 'Ĵ',
+        'K' => 'Ķ',
+        'L' => 'Ļ',
+        'M' => 'Ṁ',
+        'N' => 'Ñ',
+        'O' => 'Ö',
+        'P' => 'Þ',
+        'Q' => 'Ǫ',
+        'R' => 'Ŕ',
+        'S' => 'Š',
+        'T' => 'Ŧ',
+        'U' => 'Û',
+        'V' => 'Ṽ',
+        'W' => 'Ŵ',
+        'X' => 'Ẋ',
+        'Y' => 'Ý',
+        'Z' => 'Ž',
+        c => c,
+    }
+}
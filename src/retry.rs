@@ -0,0 +1,172 @@
+use crate::TranslateError;
+use std::time::Duration;
+
+/// How a [`Client`](crate::Client) retries requests that failed with a transient error.
+///
+/// The delay before the n-th retry is `base_delay * 2^(n - 1)`, capped at `max_delay` and shortened by a random
+/// part of up to `jitter` of itself so many clients don't retry at the same time. When the instance answers
+/// with [`TranslateError::RateLimitError`](TranslateError::RateLimitError) and a `Retry-After`, that delay is
+/// used instead, and the request is given up on if it is longer than `max_delay`.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: f32,
+    retryable: fn(&TranslateError) -> bool,
+}
+
+impl RetryPolicy {
+    /// Three attempts, waiting from 500ms up to 30s with 50% jitter, retrying [`is_transient`](RetryPolicy::is_transient) errors.
+    pub fn new() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: 0.5,
+            retryable: Self::is_transient,
+        }
+    }
+
+    /// Set how many times a request is sent at most, the first attempt included.
+    pub fn max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts.max(1);
+        self
+    }
+
+    /// Set the delay before the first retry, doubled on each of the next ones.
+    pub fn base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+        self
+    }
+
+    /// Set the longest delay between two attempts.
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Set the part of each delay (from 0 to 1) that is randomly taken off it.
+    pub fn jitter(mut self, jitter: f32) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set which errors are worth retrying. Defaults to [`is_transient`](RetryPolicy::is_transient).
+    pub fn retry_if(mut self, retryable: fn(&TranslateError) -> bool) -> Self {
+        self.retryable = retryable;
+        self
+    }
+
+    /// Whether an error may go away by itself: transport failures, timeouts, rate limiting and 5xx server errors.
+    pub fn is_transient(error: &TranslateError) -> bool {
+        matches!(
+            error,
+            TranslateError::TransportError(_)
+                | TranslateError::Timeout
+                | TranslateError::RateLimitError(_)
+                | TranslateError::ServerError(500..=599, _)
+        )
+    }
+
+    /// Whether `error` is worth retrying under this policy.
+    pub(crate) fn retries(&self, error: &TranslateError) -> bool {
+        (self.retryable)(error)
+    }
+
+    /// How long to wait before sending a request again after `attempt` attempts failed, `None` to give up.
+    pub(crate) fn delay(&self, attempt: u32, error: &TranslateError) -> Option<Duration> {
+        if attempt >= self.max_attempts || !self.retries(error) {
+            return None;
+        }
+
+        if let TranslateError::RateLimitError(Some(retry_after)) = error {
+            return (*retry_after <= self.max_delay).then_some(*retry_after);
+        }
+
+        let backoff = self
+            .base_delay
+            .checked_mul(2u32.saturating_pow(attempt - 1))
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay);
+
+        Some(backoff.mul_f32(1.0 - self.jitter * fastrand::f32()))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UNAVAILABLE: TranslateError = TranslateError::Timeout;
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy::new()
+            .max_attempts(5)
+            .base_delay(Duration::from_secs(1))
+            .max_delay(Duration::from_secs(5))
+            .jitter(0.0)
+    }
+
+    #[test]
+    fn exponential_backoff() {
+        let delays: Vec<Option<Duration>> = (1..=5)
+            .map(|attempt| policy().delay(attempt, &UNAVAILABLE))
+            .collect();
+
+        assert_eq!(
+            delays,
+            [
+                Some(Duration::from_secs(1)),
+                Some(Duration::from_secs(2)),
+                Some(Duration::from_secs(4)),
+                Some(Duration::from_secs(5)),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn jitter_shortens_delays() {
+        let policy = policy().jitter(0.5);
+
+        for _ in 0..100 {
+            let delay = policy.delay(2, &UNAVAILABLE).unwrap();
+
+            assert!(delay >= Duration::from_secs(1) && delay <= Duration::from_secs(2));
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let error = TranslateError::RateLimitError(Some(Duration::from_secs(3)));
+        assert_eq!(policy().delay(1, &error), Some(Duration::from_secs(3)));
+
+        let error = TranslateError::RateLimitError(Some(Duration::from_secs(60)));
+        assert_eq!(policy().delay(1, &error), None);
+
+        let error = TranslateError::RateLimitError(None);
+        assert_eq!(policy().delay(1, &error), Some(Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn retryable_errors() {
+        assert!(policy()
+            .delay(1, &TranslateError::ServerError(503, String::new()))
+            .is_some());
+        assert!(policy()
+            .delay(1, &TranslateError::ServerError(400, String::new()))
+            .is_none());
+        assert!(policy().delay(1, &TranslateError::LengthError).is_none());
+
+        let policy = policy().retry_if(|error| matches!(error, TranslateError::LengthError));
+        assert!(policy.delay(1, &TranslateError::LengthError).is_some());
+        assert!(policy.delay(1, &UNAVAILABLE).is_none());
+    }
+}
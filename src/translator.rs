@@ -0,0 +1,83 @@
+use crate::{Client, Detection, Language, LanguageInfo, TranslateError, Translation};
+use futures_util::future::BoxFuture;
+use std::fmt::Debug;
+
+/// Something that translates text, so code can depend on it instead of a particular [`Client`](Client).
+///
+/// [`Client`](Client) translates with a LibreTranslate instance and [`Pseudo`](crate::Pseudo) makes fake
+/// translations offline. Implement it to put a cache in front of another translator, fake one in tests or use
+/// another engine. The trait is object safe, a [`TranslationBuilder`](crate::TranslationBuilder) or a
+/// [`Query`](crate::Query) can be pointed at a `&dyn Translator` with their `translator` method.
+///
+/// ```rust
+/// use libretranslate::{Language, Pseudo, Translate, TranslateError, Translator};
+///
+/// async fn greet(translator: &dyn Translator) -> Result<String, TranslateError> {
+///     "Hello world!".to_lang(Language::French).translator(translator).translate().await
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let output = greet(&Pseudo::new()).await.unwrap();
+///
+///     assert_eq!(output, "[Ĥéļļö ŵöŕļð! ~~~]");
+/// }
+/// ```
+pub trait Translator: Debug + Send + Sync {
+    /// Translate `input` from `source`, which may be [`Language::Detect`](Language::Detect), to `target`.
+    fn translate<'a>(
+        &'a self,
+        source: Language,
+        target: Language,
+        input: &'a str,
+    ) -> BoxFuture<'a, Result<Translation, TranslateError>>;
+
+    /// Translate many texts at once, with one result per text in the same order.
+    fn translate_batch<'a>(
+        &'a self,
+        source: Language,
+        target: Language,
+        inputs: &'a [&'a str],
+    ) -> BoxFuture<'a, Vec<Result<Translation, TranslateError>>>;
+
+    /// Detect the language of `input`, ranked from the most to the least confident candidate.
+    fn detect<'a>(
+        &'a self,
+        input: &'a str,
+    ) -> BoxFuture<'a, Result<Vec<Detection>, TranslateError>>;
+
+    /// The languages that can be translated.
+    fn languages(&self) -> BoxFuture<'_, Result<Vec<LanguageInfo>, TranslateError>>;
+}
+
+/// The client's options apply, but not its default languages since every method is given them.
+impl Translator for Client {
+    fn translate<'a>(
+        &'a self,
+        source: Language,
+        target: Language,
+        input: &'a str,
+    ) -> BoxFuture<'a, Result<Translation, TranslateError>> {
+        Box::pin(self.translate_text(source, target, input))
+    }
+
+    fn translate_batch<'a>(
+        &'a self,
+        source: Language,
+        target: Language,
+        inputs: &'a [&'a str],
+    ) -> BoxFuture<'a, Vec<Result<Translation, TranslateError>>> {
+        Box::pin(self.translate_batch_text(source, target, inputs))
+    }
+
+    fn detect<'a>(
+        &'a self,
+        input: &'a str,
+    ) -> BoxFuture<'a, Result<Vec<Detection>, TranslateError>> {
+        Box::pin(Client::detect(self, input))
+    }
+
+    fn languages(&self) -> BoxFuture<'_, Result<Vec<LanguageInfo>, TranslateError>> {
+        Box::pin(Client::languages(self))
+    }
+}
@@ -0,0 +1,239 @@
+use super::{Method, Request, Response, Transport};
+use crate::TranslateError;
+use futures_util::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// A request and the response it got, as written in a cassette file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Interaction {
+    method: String,
+    /// The path of the URL, so a cassette can be replayed against any URL.
+    path: String,
+    /// The request body without API keys or multipart boundaries.
+    body: String,
+    status: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    retry_after: Option<String>,
+    response: Body,
+}
+
+/// A response body, kept readable when it is text.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum Body {
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
+impl Interaction {
+    fn matches(&self, method: &str, path: &str, body: &str) -> bool {
+        self.method == method && self.path == path && self.body == body
+    }
+}
+
+/// Sends requests with another transport and appends each of them to a cassette file.
+#[derive(Debug)]
+pub(crate) struct RecordTransport {
+    inner: Arc<dyn Transport>,
+    path: PathBuf,
+    /// Shared with the transports wrapping another HTTP client, so none of their interactions are lost.
+    interactions: Arc<Mutex<Vec<Interaction>>>,
+}
+
+impl RecordTransport {
+    pub fn new<P: AsRef<Path>>(inner: Arc<dyn Transport>, path: P) -> Self {
+        Self {
+            inner,
+            path: path.as_ref().to_path_buf(),
+            interactions: Arc::default(),
+        }
+    }
+}
+
+impl Transport for RecordTransport {
+    fn send<'a>(&'a self, request: Request<'a>) -> BoxFuture<'a, Result<Response, TranslateError>> {
+        Box::pin(async move {
+            let res = self.inner.send(request).await?;
+
+            let interaction = Interaction {
+                method: method(request.method).to_string(),
+                path: path(request.url).to_string(),
+                body: normalize(request),
+                status: res.status,
+                retry_after: res.retry_after.clone(),
+                response: match String::from_utf8(res.body.clone()) {
+                    Ok(text) => Body::Text(text),
+                    Err(error) => Body::Bytes(error.into_bytes()),
+                },
+            };
+
+            let json = {
+                let mut interactions = self
+                    .interactions
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner);
+
+                interactions.push(interaction);
+                serde_json::to_vec_pretty(&*interactions)
+            };
+
+            // Rewriting the whole cassette keeps it valid if the process stops before the client is dropped.
+            match json {
+                Ok(json) => std::fs::write(&self.path, json).map_err(TranslateError::file)?,
+                Err(error) => return Err(TranslateError::file(error)),
+            }
+
+            Ok(res)
+        })
+    }
+
+    fn with_http(self: Arc<Self>, http: Arc<dyn Transport>) -> Arc<dyn Transport> {
+        Arc::new(Self {
+            inner: self.inner.clone().with_http(http),
+            path: self.path.clone(),
+            interactions: self.interactions.clone(),
+        })
+    }
+
+    #[cfg(feature = "blocking")]
+    fn is_blocking(&self) -> bool {
+        self.inner.is_blocking()
+    }
+}
+
+/// Answers requests with the responses of a cassette file, without sending anything.
+#[derive(Debug)]
+pub(crate) struct ReplayTransport {
+    interactions: Vec<Interaction>,
+    /// How many times each interaction was replayed.
+    replayed: Mutex<Vec<usize>>,
+}
+
+impl ReplayTransport {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, TranslateError> {
+        let json = std::fs::read(path).map_err(TranslateError::file)?;
+        let interactions: Vec<Interaction> =
+            serde_json::from_slice(&json).map_err(TranslateError::file)?;
+
+        Ok(Self {
+            replayed: Mutex::new(vec![0; interactions.len()]),
+            interactions,
+        })
+    }
+
+    /// The first matching interaction that wasn't replayed yet, or the last matching one if all were.
+    fn replay(&self, request: Request<'_>) -> Option<&Interaction> {
+        let method = method(request.method);
+        let path = path(request.url);
+        let body = normalize(request);
+
+        let mut replayed = self.replayed.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let matching: Vec<usize> = self
+            .interactions
+            .iter()
+            .enumerate()
+            .filter(|(_, interaction)| interaction.matches(method, path, &body))
+            .map(|(i, _)| i)
+            .collect();
+
+        let index = matching
+            .iter()
+            .find(|i| replayed[**i] == 0)
+            .or(matching.last())
+            .copied()?;
+
+        replayed[index] += 1;
+
+        Some(&self.interactions[index])
+    }
+}
+
+impl Transport for ReplayTransport {
+    fn send<'a>(&'a self, request: Request<'a>) -> BoxFuture<'a, Result<Response, TranslateError>> {
+        Box::pin(async move {
+            let interaction = match self.replay(request) {
+                Some(interaction) => interaction,
+                None => {
+                    return Err(TranslateError::transport(format!(
+                        "No recorded interaction matches {} {}",
+                        method(request.method),
+                        path(request.url)
+                    )))
+                }
+            };
+
+            Ok(Response {
+                status: interaction.status,
+                retry_after: interaction.retry_after.clone(),
+                body: match &interaction.response {
+                    Body::Text(text) => text.clone().into_bytes(),
+                    Body::Bytes(bytes) => bytes.clone(),
+                },
+            })
+        })
+    }
+
+    fn with_http(self: Arc<Self>, _http: Arc<dyn Transport>) -> Arc<dyn Transport> {
+        self
+    }
+
+    #[cfg(feature = "blocking")]
+    fn is_blocking(&self) -> bool {
+        true
+    }
+}
+
+/// The path and query of `url` (ex. "/translate" for "https://libretranslate.com/translate").
+fn path(url: &str) -> &str {
+    let rest = match url.find("://") {
+        Some(i) => &url[i + 3..],
+        None => url,
+    };
+
+    match rest.find('/') {
+        Some(i) => &rest[i..],
+        None => "/",
+    }
+}
+
+fn method(method: Method) -> &'static str {
+    match method {
+        Method::Get => "GET",
+        Method::Post => "POST",
+    }
+}
+
+/// The request body as text, without what changes between runs or shouldn't be written down:
+/// API keys and multipart boundaries.
+fn normalize(request: Request<'_>) -> String {
+    let content_type = request.content_type.unwrap_or_default();
+
+    if content_type.starts_with("application/json") {
+        if let Ok(mut json) = serde_json::from_slice::<serde_json::Value>(request.body) {
+            if let Some(object) = json.as_object_mut() {
+                object.remove("api_key");
+            }
+
+            return json.to_string();
+        }
+    }
+
+    let mut body = String::from_utf8_lossy(request.body).into_owned();
+
+    if let Some((_, boundary)) = content_type.split_once("boundary=") {
+        body = body.replace(boundary, "boundary");
+
+        let field = "name=\"api_key\"\r\n\r\n";
+
+        if let Some(start) = body.find(field).map(|i| i + field.len()) {
+            if let Some(end) = body[start..].find("\r\n").map(|i| i + start) {
+                body.replace_range(start..end, "");
+            }
+        }
+    }
+
+    body
+}
@@ -0,0 +1,108 @@
+//! The HTTP clients requests to an instance can be sent with, one per cargo feature, and the transports that
+//! answer them without a network.
+
+use crate::TranslateError;
+use futures_util::future::BoxFuture;
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(feature = "cassette")]
+mod cassette;
+#[cfg(feature = "reqwest")]
+mod reqwest;
+#[cfg(feature = "surf")]
+mod surf;
+#[cfg(feature = "ureq")]
+mod ureq;
+
+#[cfg(feature = "cassette")]
+pub(crate) use self::cassette::{RecordTransport, ReplayTransport};
+#[cfg(feature = "reqwest")]
+pub(crate) use self::reqwest::ReqwestTransport;
+#[cfg(feature = "surf")]
+pub(crate) use self::surf::SurfTransport;
+#[cfg(feature = "ureq")]
+pub(crate) use self::ureq::UreqTransport;
+
+#[cfg(not(any(feature = "surf", feature = "reqwest", feature = "ureq")))]
+compile_error!("one of the `surf`, `reqwest` or `ureq` features must be enabled");
+
+/// An HTTP request to an instance.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Request<'a> {
+    pub method: Method,
+    pub url: &'a str,
+    pub content_type: Option<&'a str>,
+    pub body: &'a [u8],
+    /// The client times requests out itself, transports that block can't let it and need to know.
+    #[cfg_attr(not(any(feature = "reqwest", feature = "ureq")), allow(dead_code))]
+    pub timeout: Option<Duration>,
+}
+
+impl<'a> Request<'a> {
+    pub fn get(url: &'a str) -> Self {
+        Self {
+            method: Method::Get,
+            url,
+            content_type: None,
+            body: &[],
+            timeout: None,
+        }
+    }
+
+    pub fn post(url: &'a str, content_type: &'a str, body: &'a [u8]) -> Self {
+        Self {
+            method: Method::Post,
+            url,
+            content_type: Some(content_type),
+            body,
+            timeout: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Method {
+    Get,
+    Post,
+}
+
+/// The parts of an HTTP response the client looks at.
+#[derive(Debug, Clone)]
+pub(crate) struct Response {
+    pub status: u16,
+    pub retry_after: Option<String>,
+    pub body: Vec<u8>,
+}
+
+/// Sends requests with an HTTP client.
+///
+/// Transports only fail when no response was received, error statuses are returned as responses.
+pub(crate) trait Transport: Debug + Send + Sync {
+    fn send<'a>(&'a self, request: Request<'a>) -> BoxFuture<'a, Result<Response, TranslateError>>;
+
+    /// This transport sending requests with the HTTP client `http` instead. An HTTP client is replaced by it,
+    /// a transport wrapping one rewraps it and one that doesn't send anything stays as is.
+    fn with_http(self: Arc<Self>, http: Arc<dyn Transport>) -> Arc<dyn Transport> {
+        http
+    }
+
+    /// Whether requests block the calling thread instead of waiting for a runtime.
+    #[cfg(feature = "blocking")]
+    fn is_blocking(&self) -> bool {
+        false
+    }
+}
+
+/// The transport of the enabled feature, `surf` first, then `reqwest`, then `ureq`.
+pub(crate) fn default(connect_timeout: Option<Duration>) -> Arc<dyn Transport> {
+    #[cfg(feature = "surf")]
+    return Arc::new(SurfTransport::new(connect_timeout));
+
+    #[cfg(all(feature = "reqwest", not(feature = "surf")))]
+    return Arc::new(ReqwestTransport::new(connect_timeout));
+
+    #[cfg(all(feature = "ureq", not(any(feature = "surf", feature = "reqwest"))))]
+    return Arc::new(UreqTransport::new(connect_timeout));
+}
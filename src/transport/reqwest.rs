@@ -0,0 +1,86 @@
+use super::{Method, Request, Response, Transport};
+use crate::TranslateError;
+use futures_util::future::BoxFuture;
+use std::time::Duration;
+
+/// Sends requests with [`reqwest`](::reqwest), on the tokio runtime.
+#[derive(Debug, Clone)]
+pub(crate) struct ReqwestTransport {
+    http: ::reqwest::Client,
+}
+
+impl ReqwestTransport {
+    // Only the default transport when no preferred backend is enabled.
+    #[cfg_attr(feature = "surf", allow(dead_code))]
+    pub fn new(connect_timeout: Option<Duration>) -> Self {
+        let mut builder = ::reqwest::Client::builder();
+
+        if let Some(timeout) = connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+
+        // Building only fails if the TLS backend can't be initialized, then so would the default client.
+        match builder.build() {
+            Ok(http) => Self::from(http),
+            Err(_) => Self::from(::reqwest::Client::new()),
+        }
+    }
+}
+
+impl From<::reqwest::Client> for ReqwestTransport {
+    fn from(http: ::reqwest::Client) -> Self {
+        Self { http }
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn send<'a>(&'a self, request: Request<'a>) -> BoxFuture<'a, Result<Response, TranslateError>> {
+        Box::pin(async move {
+            let mut builder = match request.method {
+                Method::Get => self.http.get(request.url),
+                Method::Post => self.http.post(request.url),
+            };
+
+            if let Some(timeout) = request.timeout {
+                builder = builder.timeout(timeout);
+            }
+
+            if let Some(content_type) = request.content_type {
+                builder = builder
+                    .header(::reqwest::header::CONTENT_TYPE, content_type)
+                    .body(request.body.to_vec());
+            }
+
+            let res = match builder.send().await {
+                Ok(res) => res,
+                Err(error) => return Err(transport(error)),
+            };
+
+            let status = res.status().as_u16();
+            let retry_after = res
+                .headers()
+                .get(::reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string);
+
+            let body = match res.bytes().await {
+                Ok(body) => body.to_vec(),
+                Err(error) => return Err(transport(error)),
+            };
+
+            Ok(Response {
+                status,
+                retry_after,
+                body,
+            })
+        })
+    }
+}
+
+fn transport(error: ::reqwest::Error) -> TranslateError {
+    if error.is_timeout() {
+        return TranslateError::Timeout;
+    }
+
+    TranslateError::transport(error)
+}
@@ -0,0 +1,84 @@
+use super::{Method, Request, Response, Transport};
+use crate::TranslateError;
+use ::surf::http::Method as SurfMethod;
+use futures_util::future::BoxFuture;
+use http_client::isahc::IsahcClient;
+use isahc::config::Configurable;
+use std::time::Duration;
+
+/// Sends requests with [`surf`](::surf), on the curl based client.
+#[derive(Debug, Clone)]
+pub(crate) struct SurfTransport {
+    http: ::surf::Client,
+}
+
+impl SurfTransport {
+    pub fn new(connect_timeout: Option<Duration>) -> Self {
+        let timeout = match connect_timeout {
+            Some(timeout) => timeout,
+            None => return Self::from(::surf::Client::new()),
+        };
+
+        // Building only fails if curl can't be initialized, then so would the default client.
+        match isahc::HttpClient::builder()
+            .connect_timeout(timeout)
+            .build()
+        {
+            Ok(http) => Self::from(::surf::Client::with_http_client(IsahcClient::from_client(
+                http,
+            ))),
+            Err(_) => Self::from(::surf::Client::new()),
+        }
+    }
+}
+
+impl From<::surf::Client> for SurfTransport {
+    fn from(http: ::surf::Client) -> Self {
+        Self { http }
+    }
+}
+
+impl Transport for SurfTransport {
+    fn send<'a>(&'a self, request: Request<'a>) -> BoxFuture<'a, Result<Response, TranslateError>> {
+        Box::pin(async move {
+            let method = match request.method {
+                Method::Get => SurfMethod::Get,
+                Method::Post => SurfMethod::Post,
+            };
+
+            let mut builder = self.http.request(method, request.url);
+
+            if let Some(content_type) = request.content_type {
+                builder = builder
+                    .content_type(content_type)
+                    .body(request.body.to_vec());
+            }
+
+            let mut res = match builder.await {
+                Ok(res) => res,
+                Err(error) => return Err(transport(error)),
+            };
+
+            let body = match res.body_bytes().await {
+                Ok(body) => body,
+                Err(error) => return Err(transport(error)),
+            };
+
+            Ok(Response {
+                status: res.status().into(),
+                retry_after: res
+                    .header("Retry-After")
+                    .map(|values| values.last().as_str().to_string()),
+                body,
+            })
+        })
+    }
+}
+
+fn transport(error: ::surf::Error) -> TranslateError {
+    if let Some(isahc::Error::Timeout) = error.downcast_ref() {
+        return TranslateError::Timeout;
+    }
+
+    TranslateError::transport(error.into_inner())
+}
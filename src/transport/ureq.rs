@@ -0,0 +1,98 @@
+use super::{Method, Request, Response, Transport};
+use crate::TranslateError;
+use futures_util::future::BoxFuture;
+use std::io::{ErrorKind, Read};
+use std::time::Duration;
+
+/// Sends requests with [`ureq`](::ureq). Requests block the calling thread, even from async code.
+#[derive(Debug, Clone)]
+pub(crate) struct UreqTransport {
+    agent: ::ureq::Agent,
+}
+
+impl UreqTransport {
+    // Only the default transport when no preferred backend is enabled.
+    #[cfg_attr(any(feature = "surf", feature = "reqwest"), allow(dead_code))]
+    pub fn new(connect_timeout: Option<Duration>) -> Self {
+        let mut builder = ::ureq::AgentBuilder::new();
+
+        if let Some(timeout) = connect_timeout {
+            builder = builder.timeout_connect(timeout);
+        }
+
+        Self::from(builder.build())
+    }
+
+    /// Send a request, blocking until the response is received.
+    pub fn send_blocking(&self, request: Request<'_>) -> Result<Response, TranslateError> {
+        let mut builder = match request.method {
+            Method::Get => self.agent.get(request.url),
+            Method::Post => self.agent.post(request.url),
+        };
+
+        if let Some(timeout) = request.timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        let res = match request.content_type {
+            Some(content_type) => builder
+                .set("Content-Type", content_type)
+                .send_bytes(request.body),
+            None => builder.call(),
+        };
+
+        let res = match res {
+            Ok(res) => res,
+            // Error statuses still come with a body to decode.
+            Err(::ureq::Error::Status(_, res)) => res,
+            Err(::ureq::Error::Transport(error)) => return Err(transport(error)),
+        };
+
+        let status = res.status();
+        let retry_after = res.header("Retry-After").map(str::to_string);
+
+        let mut body = Vec::new();
+
+        if let Err(error) = res.into_reader().read_to_end(&mut body) {
+            return Err(match error.kind() {
+                ErrorKind::TimedOut | ErrorKind::WouldBlock => TranslateError::Timeout,
+                _ => TranslateError::transport(error),
+            });
+        }
+
+        Ok(Response {
+            status,
+            retry_after,
+            body,
+        })
+    }
+}
+
+impl From<::ureq::Agent> for UreqTransport {
+    fn from(agent: ::ureq::Agent) -> Self {
+        Self { agent }
+    }
+}
+
+impl Transport for UreqTransport {
+    fn send<'a>(&'a self, request: Request<'a>) -> BoxFuture<'a, Result<Response, TranslateError>> {
+        Box::pin(async move { self.send_blocking(request) })
+    }
+
+    #[cfg(feature = "blocking")]
+    fn is_blocking(&self) -> bool {
+        true
+    }
+}
+
+fn transport(error: ::ureq::Transport) -> TranslateError {
+    let timed_out = std::error::Error::source(&error)
+        .and_then(|source| source.downcast_ref::<std::io::Error>())
+        .is_some_and(|error| matches!(error.kind(), ErrorKind::TimedOut | ErrorKind::WouldBlock));
+
+    if timed_out {
+        return TranslateError::Timeout;
+    }
+
+    TranslateError::transport(error)
+}
@@ -0,0 +1,96 @@
+use libretranslate::mock::MockServer;
+use libretranslate::{Client, Language, TranslateError};
+use std::time::Duration;
+
+// Nothing listens here, so replayed clients can't reach a server by accident.
+const DEAD_URL: &str = "http://127.0.0.1:9/";
+
+#[tokio::test]
+async fn record_and_replay() {
+    let path = std::env::temp_dir().join(format!("libretranslate-{}.json", std::process::id()));
+
+    let server = MockServer::start();
+    server.key("secret");
+    server.detect_as(Language::German);
+
+    let client = Client::new(server.url())
+        .key("secret")
+        .from_lang(Language::English)
+        .to_lang(Language::French)
+        .record(&path)
+        // Still sent with a new HTTP client, and recorded.
+        .connect_timeout(Duration::from_secs(5));
+
+    let recorded = client.translate("Hello").await.unwrap();
+    let detections = client.detect("Hallo").await.unwrap();
+    let bytes = client
+        .translate_file_bytes("hello.txt", b"Hello", Language::English, Language::French)
+        .await
+        .unwrap();
+
+    let cassette = std::fs::read_to_string(&path).unwrap();
+    assert!(!cassette.contains("secret"));
+
+    let client = Client::new(DEAD_URL)
+        .key("other")
+        .from_lang(Language::English)
+        .to_lang(Language::French)
+        .replay(&path)
+        .unwrap()
+        .connect_timeout(Duration::from_secs(5));
+
+    let replayed = client.translate("Hello").await.unwrap();
+    assert_eq!(replayed.output, recorded.output);
+
+    let replayed = client.detect("Hallo").await.unwrap();
+    assert_eq!(replayed[0].language, detections[0].language);
+
+    let replayed = client
+        .translate_file_bytes("hello.txt", b"Hello", Language::English, Language::French)
+        .await
+        .unwrap();
+    assert_eq!(replayed, bytes);
+
+    let error = client.translate("Goodbye").await.unwrap_err();
+    assert!(matches!(error, TranslateError::TransportError(_)));
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[cfg(feature = "blocking")]
+#[test]
+fn record_blocking() {
+    let path = std::env::temp_dir().join(format!(
+        "libretranslate-blocking-{}.json",
+        std::process::id()
+    ));
+
+    let server = MockServer::start();
+
+    let client = Client::new(server.url())
+        .connect_timeout(Duration::from_secs(5))
+        .record(&path);
+
+    let data = client
+        .text("Hello")
+        .from_lang(Language::English)
+        .to_lang(Language::French)
+        .translate_blocking()
+        .unwrap();
+
+    assert_eq!(data.output, "[fr] Hello");
+
+    let cassette = std::fs::read_to_string(&path).unwrap();
+    assert!(cassette.contains("/translate"));
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn missing_cassette() {
+    let error = Client::new(DEAD_URL)
+        .replay("does-not-exist.json")
+        .unwrap_err();
+
+    assert!(matches!(error, TranslateError::FileError(_)));
+}
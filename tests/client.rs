@@ -0,0 +1,466 @@
+use libretranslate::mock::MockServer;
+use libretranslate::{
+    translate_url, Client, Format, Instance, Language, RetryPolicy, TranslateError,
+    TranslationBuilder,
+};
+use std::time::{Duration, Instant};
+
+fn count(server: &MockServer, path: &str) -> usize {
+    server
+        .requests()
+        .iter()
+        .filter(|request| request.path == path)
+        .count()
+}
+
+#[tokio::test]
+async fn translate() {
+    let server = MockServer::start();
+    server.translation("Hello world!", "Bonjour le monde !");
+
+    let data = translate_url(
+        Language::English,
+        Language::French,
+        "Hello world!",
+        server.url(),
+        None,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(data.output, "Bonjour le monde !");
+    assert_eq!(data.source, Language::English);
+    assert_eq!(data.confidence, None);
+    assert_eq!(data.url, format!("{}translate", server.url()));
+}
+
+#[tokio::test]
+async fn translate_empty() {
+    let server = MockServer::start();
+
+    let data = TranslationBuilder::new()
+        .url(server.url())
+        .from_lang(Language::English)
+        .to_lang(Language::French)
+        .text("")
+        .translate()
+        .await
+        .unwrap();
+
+    // Nothing is sent, but the translation looks like one from the instance.
+    assert_eq!(data.output, "");
+    assert_eq!(data.url, format!("{}translate", server.url()));
+    assert_eq!(count(&server, "/translate"), 0);
+}
+
+#[tokio::test]
+async fn translate_detected() {
+    let server = MockServer::start();
+    server.detect_as(Language::German);
+
+    let data = TranslationBuilder::new()
+        .url(server.url())
+        .to_lang(Language::French)
+        .text("Hallo Welt!")
+        .alternatives(2)
+        .translate()
+        .await
+        .unwrap();
+
+    assert_eq!(data.output, "[fr] Hallo Welt!");
+    assert_eq!(data.source, Language::German);
+    assert_eq!(data.confidence, Some(90.0));
+    assert_eq!(data.alternatives.len(), 2);
+}
+
+#[tokio::test]
+async fn detect() {
+    let server = MockServer::start();
+    server.detect_as(Language::Spanish);
+
+    let detections = Client::new(server.url()).detect("Hola").await.unwrap();
+
+    assert_eq!(detections[0].language, Language::Spanish);
+
+    let error = Client::new(server.url())
+        .min_confidence(95.0)
+        .detect("Hola")
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, TranslateError::DetectError));
+}
+
+#[tokio::test]
+async fn batch() {
+    let server = MockServer::start();
+
+    let client = Client::new(server.url())
+        .from_lang(Language::English)
+        .to_lang(Language::Italian);
+
+    let results = client.translate_batch(["One", "Two", "Three"]).await;
+    let outputs: Vec<String> = results
+        .into_iter()
+        .map(|data| data.unwrap().output)
+        .collect();
+
+    assert_eq!(outputs, ["[it] One", "[it] Two", "[it] Three"]);
+    assert_eq!(count(&server, "/translate"), 1);
+}
+
+#[tokio::test]
+async fn chunked() {
+    let server = MockServer::start();
+    server.char_limit(Some(30));
+
+    let text = "The first sentence is here. The second one follows.\n\nA new paragraph starts.";
+
+    let client = Client::new(server.url())
+        .from_lang(Language::English)
+        .to_lang(Language::French);
+
+    let error = client.translate(text).await.unwrap_err();
+    assert!(matches!(error, TranslateError::LengthError));
+
+    let data = client.chunked(true).translate(text).await.unwrap();
+
+    assert_eq!(
+        data.output,
+        "[fr] The first sentence is here. [fr] The second one follows.\n\n[fr] A new paragraph starts."
+    );
+    assert_eq!(count(&server, "/translate"), 3);
+}
+
+#[tokio::test]
+async fn chunked_batch() {
+    let server = MockServer::start();
+    server.char_limit(Some(30));
+
+    let client = Client::new(server.url())
+        .from_lang(Language::English)
+        .to_lang(Language::French);
+
+    let inputs = [
+        "Short.",
+        "The first sentence is here. The second one follows.",
+    ];
+
+    let error = client.translate_batch(inputs).await.remove(1).unwrap_err();
+    assert!(matches!(error, TranslateError::LengthError));
+
+    let outputs: Vec<String> = client
+        .chunked(true)
+        .translate_batch(inputs)
+        .await
+        .into_iter()
+        .map(|data| data.unwrap().output)
+        .collect();
+
+    assert_eq!(
+        outputs,
+        [
+            "[fr] Short.",
+            "[fr] The first sentence is here. [fr] The second one follows."
+        ]
+    );
+}
+
+#[tokio::test]
+async fn chunked_html() {
+    let server = MockServer::start();
+    server.char_limit(Some(25));
+
+    let client = Client::new(server.url())
+        .from_lang(Language::English)
+        .to_lang(Language::French)
+        .format(Format::Html);
+
+    // Like an instance, the mock parses each text as a document of its own.
+    let data = client.translate("<b>Hello</i> there").await.unwrap();
+    assert_eq!(data.output, "[fr] <b>Hello there</b>");
+
+    let client = client.chunked(true);
+
+    // Pieces are only cut where no element is open, so the markup comes back intact.
+    let data = client
+        .translate("<p>Hello there</p>\n\n<p>Hi <b>Kenobi</b></p>")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        data.output,
+        "[fr] <p>Hello there</p>\n\n[fr] <p>Hi <b>Kenobi</b></p>"
+    );
+
+    let data = client
+        .translate("Hello there <b>my</b> old friend")
+        .await
+        .unwrap();
+
+    assert_eq!(data.output, "[fr] Hello there <b>my</b> [fr] old friend");
+
+    let error = client
+        .translate(r#"<a href="x" class="y">Hello there friend</a>"#)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, TranslateError::LengthError));
+}
+
+#[tokio::test]
+async fn key() {
+    let server = MockServer::start();
+    server.key("secret");
+
+    let client = Client::new(server.url())
+        .from_lang(Language::English)
+        .to_lang(Language::French);
+
+    // The instance says it needs a key, so nothing is sent without one.
+    let error = client.translate("Hello").await.unwrap_err();
+    assert!(matches!(error, TranslateError::KeyError(_)));
+    assert_eq!(count(&server, "/translate"), 0);
+
+    let error = client
+        .clone()
+        .key("wrong")
+        .translate("Hello")
+        .await
+        .unwrap_err();
+    assert!(matches!(error, TranslateError::KeyError(_)));
+
+    let data = client.key("secret").translate("Hello").await.unwrap();
+    assert_eq!(data.output, "[fr] Hello");
+}
+
+#[tokio::test]
+async fn settings_unavailable() {
+    let server = MockServer::start();
+    server.fail("/frontend/settings", 503, "Service Unavailable");
+
+    let client = Client::new(server.url())
+        .from_lang(Language::English)
+        .to_lang(Language::French)
+        .char_limit(30)
+        .chunked(true)
+        .retry(RetryPolicy::new().base_delay(Duration::from_millis(10)));
+
+    let text = "The first sentence is here. The second one follows.\n\nA new paragraph starts.";
+    client.translate(text).await.unwrap();
+
+    // Asked for once without retries, not once per piece.
+    assert_eq!(count(&server, "/frontend/settings"), 1);
+    assert_eq!(count(&server, "/translate"), 3);
+
+    // Nor again right away.
+    client.translate("Hello").await.unwrap();
+    assert_eq!(count(&server, "/frontend/settings"), 1);
+}
+
+#[tokio::test]
+async fn unsupported_language() {
+    let server = MockServer::start();
+    server.languages([Language::English, Language::French]);
+
+    let client = Client::new(server.url()).from_lang(Language::English);
+
+    let error = client
+        .clone()
+        .to_lang(Language::German)
+        .translate("Hello")
+        .await
+        .unwrap_err();
+    assert!(matches!(error, TranslateError::UnsupportedError(_)));
+
+    let languages = client.languages().await.unwrap();
+    assert_eq!(languages.len(), 2);
+
+    // Now that the languages are known, the pair is checked before sending anything.
+    let error = client
+        .to_lang(Language::German)
+        .translate("Hello")
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        TranslateError::PairError(Language::English, Language::German)
+    ));
+}
+
+#[tokio::test]
+async fn server_errors() {
+    let server = MockServer::start();
+    server.fail("/translate", 500, "Something broke");
+
+    let client = Client::new(server.url())
+        .from_lang(Language::English)
+        .to_lang(Language::French);
+
+    match client.translate("Hello").await.unwrap_err() {
+        TranslateError::ServerError(status, message) => {
+            assert_eq!(status, 500);
+            assert_eq!(message, "Something broke");
+        }
+        error => panic!("unexpected error: {:?}", error),
+    }
+
+    server.rate_limit(1, Duration::from_secs(30));
+
+    client.translate("Hello").await.unwrap();
+
+    match client.translate("Hello").await.unwrap_err() {
+        TranslateError::RateLimitError(Some(retry_after)) => {
+            assert!(retry_after <= Duration::from_secs(30));
+        }
+        error => panic!("unexpected error: {:?}", error),
+    }
+}
+
+#[tokio::test]
+async fn retry() {
+    let server = MockServer::start();
+    server
+        .fail("/translate", 502, "Bad Gateway")
+        .fail("/translate", 503, "Service Unavailable");
+
+    let policy = RetryPolicy::new().base_delay(Duration::from_millis(10));
+
+    let data = Client::new(server.url())
+        .from_lang(Language::English)
+        .to_lang(Language::French)
+        .retry(policy)
+        .translate("Hello")
+        .await
+        .unwrap();
+
+    assert_eq!(data.output, "[fr] Hello");
+    assert_eq!(count(&server, "/translate"), 3);
+}
+
+#[tokio::test]
+async fn fallback() {
+    let primary = MockServer::start();
+    let secondary = MockServer::start();
+    primary.fail("/translate", 503, "Service Unavailable");
+
+    let client = Client::new(primary.url())
+        .fallback(Instance::new(secondary.url()))
+        .from_lang(Language::English)
+        .to_lang(Language::French);
+
+    let data = client.translate("Hello").await.unwrap();
+
+    assert_eq!(data.output, "[fr] Hello");
+    assert!(data.url.starts_with(secondary.url()));
+    assert_eq!(count(&primary, "/translate"), 1);
+}
+
+#[tokio::test]
+async fn timeout() {
+    let server = MockServer::start();
+    server.latency(Duration::from_millis(500));
+
+    let error = TranslationBuilder::new()
+        .url(server.url())
+        .from_lang(Language::English)
+        .to_lang(Language::French)
+        .text("Hello")
+        .timeout(Duration::from_millis(50))
+        .translate()
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, TranslateError::Timeout));
+}
+
+#[tokio::test]
+async fn timeout_with_settings() {
+    let server = MockServer::start();
+    server.latency(Duration::from_millis(500));
+
+    let client = Client::new(server.url())
+        .from_lang(Language::English)
+        .to_lang(Language::French)
+        .timeout(Duration::from_millis(100));
+
+    // The settings time out first, and the translation isn't sent after them.
+    let start = Instant::now();
+    let error = client.translate("Hello").await.unwrap_err();
+
+    assert!(matches!(error, TranslateError::Timeout));
+    assert!(start.elapsed() < Duration::from_millis(200));
+    assert_eq!(count(&server, "/translate"), 0);
+}
+
+#[tokio::test]
+async fn suggest() {
+    let server = MockServer::start();
+
+    let client = Client::new(server.url())
+        .from_lang(Language::English)
+        .to_lang(Language::French);
+
+    let data = client.translate("Hello").await.unwrap();
+    data.suggest(&client, "Bonjour").await.unwrap();
+
+    server.suggestions(false);
+
+    let error = data.suggest(&client, "Salut").await.unwrap_err();
+    assert!(matches!(error, TranslateError::ServerError(403, _)));
+}
+
+#[tokio::test]
+async fn translate_file() {
+    let server = MockServer::start();
+
+    let client = Client::new(server.url());
+
+    let bytes = client
+        .translate_file_bytes("hello.txt", b"Hello", Language::English, Language::French)
+        .await
+        .unwrap();
+
+    assert_eq!(bytes, b"[fr] Hello");
+
+    let error = client
+        .translate_file_bytes("hello.pdf", b"%PDF", Language::English, Language::French)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, TranslateError::FileError(_)));
+}
+
+#[tokio::test]
+async fn translate_file_retry() {
+    let server = MockServer::start();
+    server.fail("/download_file/0", 503, "Service Unavailable");
+
+    let bytes = Client::new(server.url())
+        .retry(RetryPolicy::new().base_delay(Duration::from_millis(10)))
+        .translate_file_bytes("hello.txt", b"Hello", Language::English, Language::French)
+        .await
+        .unwrap();
+
+    assert_eq!(bytes, b"[fr] Hello");
+    assert_eq!(count(&server, "/download_file/0"), 2);
+}
+
+#[cfg(feature = "blocking")]
+#[test]
+fn blocking() {
+    use libretranslate::blocking;
+
+    let server = MockServer::start();
+
+    let data = blocking::translate_url(
+        Language::English,
+        Language::French,
+        "Hello",
+        server.url(),
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(data.output, "[fr] Hello");
+}
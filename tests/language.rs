@@ -0,0 +1,126 @@
+use libretranslate::{Language, LanguageCode, LanguageError};
+use std::collections::HashSet;
+
+#[test]
+fn codes_round_trip() {
+    for language in Language::all() {
+        assert_eq!(Ok(*language), language.as_code().parse::<Language>());
+        assert_eq!(
+            Ok(*language),
+            language.as_code().to_uppercase().parse::<Language>()
+        );
+    }
+}
+
+#[test]
+fn names_round_trip() {
+    for language in Language::all() {
+        assert_eq!(Ok(*language), language.as_pretty().parse::<Language>());
+        assert_eq!(
+            Ok(*language),
+            language.as_pretty().to_lowercase().parse::<Language>()
+        );
+    }
+}
+
+#[test]
+fn display_is_code() {
+    assert_eq!("auto", Language::Detect.to_string());
+
+    for language in Language::all() {
+        assert_eq!(language.as_code(), language.to_string());
+    }
+}
+
+#[test]
+fn codes_and_names_are_unique() {
+    let codes: HashSet<_> = Language::all().iter().map(Language::as_code).collect();
+    let names: HashSet<_> = Language::all().iter().map(Language::as_pretty).collect();
+
+    assert_eq!(Language::all().len(), codes.len());
+    assert_eq!(Language::all().len(), names.len());
+}
+
+#[test]
+fn detect_is_not_listed() {
+    assert!(!Language::all().contains(&Language::Detect));
+    assert_eq!(Ok(Language::Detect), "auto".parse::<Language>());
+}
+
+#[test]
+fn unknown_language() {
+    assert!("klingon".parse::<Language>().is_err());
+}
+
+#[cfg(feature = "unicode_langid")]
+#[test]
+fn unic_langid_round_trip() {
+    use unic_langid::LanguageIdentifier;
+
+    for language in Language::all() {
+        if let Ok(li) = language.as_code().parse::<LanguageIdentifier>() {
+            assert_eq!(Ok(*language), Language::from_unic_langid(li));
+        }
+    }
+}
+
+#[test]
+fn other_codes() {
+    let language = Language::from_code("zh-Hant").unwrap();
+
+    assert_eq!(
+        Language::Other(LanguageCode::new("zh-Hant").unwrap()),
+        language
+    );
+    assert_eq!("zh-Hant", language.as_code());
+    assert_eq!("zh-Hant", language.to_string());
+    assert_eq!(Ok(Language::French), Language::from_code("fr"));
+    assert_eq!(Ok(Language::Detect), Language::from_code("auto"));
+}
+
+#[test]
+fn other_codes_are_unique() {
+    // Known codes only have their variant.
+    assert_eq!(
+        Err(LanguageError::VariantError(Language::French)),
+        LanguageCode::new("FR")
+    );
+    assert_eq!(
+        Err(LanguageError::VariantError(Language::Detect)),
+        LanguageCode::new("auto")
+    );
+
+    // And other codes are the same whatever their case.
+    let upper = Language::from_code("ZH-HANT").unwrap();
+    let lower = Language::from_code("zh-Hant").unwrap();
+
+    assert_eq!(upper, lower);
+    assert_eq!("ZH-HANT", upper.as_code());
+    assert_eq!(1, [upper, lower].iter().collect::<HashSet<_>>().len());
+}
+
+#[test]
+fn invalid_codes() {
+    assert!(LanguageCode::new("").is_err());
+    assert!(LanguageCode::new("x").is_err());
+    assert!(LanguageCode::new("not a code").is_err());
+    assert!(LanguageCode::new("-en").is_err());
+    assert!(LanguageCode::new("abcdefghijklmnop").is_err());
+}
+
+#[test]
+fn serde_round_trip() {
+    let languages = vec![
+        Language::Detect,
+        Language::French,
+        Language::from_code("zh-Hant").unwrap(),
+    ];
+
+    let json = serde_json::to_string(&languages).unwrap();
+    assert_eq!(r#"["auto","fr","zh-Hant"]"#, json);
+    assert_eq!(
+        languages,
+        serde_json::from_str::<Vec<Language>>(&json).unwrap()
+    );
+    assert!(serde_json::from_str::<Language>(r#""not a code""#).is_err());
+}
@@ -0,0 +1,135 @@
+use libretranslate::{Client, Language, Pseudo, Translate, TranslateError, TranslationBuilder};
+use std::time::Duration;
+
+#[test]
+fn localize() {
+    let pseudo = Pseudo::new();
+
+    assert_eq!(
+        pseudo.localize("This is text", Language::French),
+        "[Ŧĥîš îš ţéxţ ~~~]"
+    );
+    assert_eq!(
+        pseudo
+            .expansion(0.0)
+            .localize("This is text", Language::French),
+        "[Ŧĥîš îš ţéxţ]"
+    );
+    assert_eq!(
+        pseudo.expansion(1.0).localize("Hi", Language::French),
+        "[Ĥî ~~]"
+    );
+}
+
+#[test]
+fn placeholders() {
+    let pseudo = Pseudo::new().expansion(0.0);
+
+    assert_eq!(
+        pseudo.localize(
+            "Hello {name}, you have %d new <b>messages</b> &amp; %1$s",
+            Language::German
+        ),
+        "[Ĥéļļö {name}, ýöû ĥáṽé %d ñéŵ <b>ɱéššáĝéš</b> &amp; %1$s]"
+    );
+    assert_eq!(
+        pseudo.localize("{{count}} at 100% of ${total}", Language::German),
+        "[{{count}} áţ 100% öƒ ${total}]"
+    );
+    assert_eq!(
+        pseudo
+            .placeholders(false)
+            .localize("Hi {name}", Language::German),
+        "[Ĥî {ñáɱé}]"
+    );
+}
+
+#[test]
+fn rtl() {
+    let pseudo = Pseudo::new().expansion(0.0);
+
+    assert_eq!(
+        pseudo.localize("Hi {name}", Language::Arabic),
+        "\u{200F}[\u{202E}Ĥî \u{202C}{name}]"
+    );
+    assert_eq!(
+        pseudo.rtl(false).localize("Hi {name}", Language::Arabic),
+        "[Ĥî {name}]"
+    );
+}
+
+#[tokio::test]
+async fn client() {
+    let client = Client::pseudo(Pseudo::new())
+        .from_lang(Language::English)
+        .to_lang(Language::French);
+
+    let data = client.translate("This is text").await.unwrap();
+
+    assert_eq!(data.output, "[Ŧĥîš îš ţéxţ ~~~]");
+    assert_eq!(data.input, "This is text");
+    assert_eq!(data.target, Language::French);
+
+    let outputs: Vec<String> = client
+        .translate_batch(["One", "Two"])
+        .await
+        .into_iter()
+        .map(|data| data.unwrap().output)
+        .collect();
+
+    assert_eq!(outputs, ["[Öñé]", "[Ŧŵö]"]);
+
+    let detections = client.detect("Anything").await.unwrap();
+    assert_eq!(detections[0].language, Language::English);
+
+    assert_eq!(
+        client.languages().await.unwrap().len(),
+        Language::all().len()
+    );
+
+    let error = client
+        .translate_file_bytes("hello.txt", b"Hello", Language::English, Language::French)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, TranslateError::FileError(_)));
+
+    let error = client.info().await.unwrap_err();
+    assert!(matches!(error, TranslateError::TransportError(_)));
+}
+
+#[tokio::test]
+async fn builders() {
+    let client = Client::pseudo(Pseudo::new().expansion(0.0));
+
+    let data = TranslationBuilder::new()
+        .client(&client)
+        .to_lang(Language::Arabic)
+        .text("Hi")
+        .translate()
+        .await
+        .unwrap();
+
+    assert_eq!(data.output, "\u{200F}[\u{202E}Ĥî\u{202C}]");
+    assert_eq!(data.source, Language::English);
+
+    let output = "Hello"
+        .to_lang(Language::Spanish)
+        .client(&client)
+        .translate()
+        .await
+        .unwrap();
+
+    assert_eq!(output, "[Ĥéļļö]");
+
+    // Settings that only matter to an instance don't change anything.
+    let data = client
+        .clone()
+        .connect_timeout(Duration::from_secs(1))
+        .text("")
+        .translate()
+        .await
+        .unwrap();
+
+    assert_eq!(data.output, "");
+    assert_eq!(data.url, "pseudo://localization/translate");
+}
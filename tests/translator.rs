@@ -0,0 +1,128 @@
+use futures_util::future::{self, BoxFuture};
+use libretranslate::mock::MockServer;
+use libretranslate::{
+    Client, Detection, Language, LanguageInfo, Pseudo, Translate, TranslateError, Translation,
+    TranslationBuilder, Translator,
+};
+
+/// Translates by shouting, to check the builders don't need a `Client`.
+#[derive(Debug)]
+struct Shout;
+
+impl Translator for Shout {
+    fn translate<'a>(
+        &'a self,
+        source: Language,
+        target: Language,
+        input: &'a str,
+    ) -> BoxFuture<'a, Result<Translation, TranslateError>> {
+        Box::pin(future::ready(Ok(Translation {
+            url: String::from("shout://"),
+            source,
+            target,
+            input: input.to_string(),
+            output: input.to_uppercase(),
+            confidence: None,
+            alternatives: Vec::new(),
+        })))
+    }
+
+    fn translate_batch<'a>(
+        &'a self,
+        source: Language,
+        target: Language,
+        inputs: &'a [&'a str],
+    ) -> BoxFuture<'a, Vec<Result<Translation, TranslateError>>> {
+        Box::pin(future::join_all(
+            inputs
+                .iter()
+                .map(|input| self.translate(source, target, input)),
+        ))
+    }
+
+    fn detect<'a>(
+        &'a self,
+        _input: &'a str,
+    ) -> BoxFuture<'a, Result<Vec<Detection>, TranslateError>> {
+        Box::pin(future::ready(Err(TranslateError::DetectError)))
+    }
+
+    fn languages(&self) -> BoxFuture<'_, Result<Vec<LanguageInfo>, TranslateError>> {
+        Box::pin(future::ready(Ok(Vec::new())))
+    }
+}
+
+#[tokio::test]
+async fn builders() {
+    let translator: &dyn Translator = &Shout;
+
+    let data = TranslationBuilder::new()
+        .url("http://127.0.0.1:9/")
+        .from_lang(Language::English)
+        .to_lang(Language::French)
+        .text("Hello")
+        .translator(translator)
+        .translate()
+        .await
+        .unwrap();
+
+    assert_eq!(data.output, "HELLO");
+    assert_eq!(data.url, "shout://");
+
+    let output = "Hello"
+        .to_lang(Language::German)
+        .translator(translator)
+        .translate()
+        .await
+        .unwrap();
+
+    assert_eq!(output, "HELLO");
+}
+
+#[tokio::test]
+async fn client() {
+    let server = MockServer::start();
+    server.detect_as(Language::Spanish);
+
+    let client = Client::new(server.url());
+    let translator: &dyn Translator = &client;
+
+    let data = translator
+        .translate(Language::English, Language::French, "Hello")
+        .await
+        .unwrap();
+    assert_eq!(data.output, "[fr] Hello");
+
+    let outputs: Vec<String> = translator
+        .translate_batch(Language::English, Language::Italian, &["One", "Two"])
+        .await
+        .into_iter()
+        .map(|data| data.unwrap().output)
+        .collect();
+    assert_eq!(outputs, ["[it] One", "[it] Two"]);
+
+    let detections = translator.detect("Hola").await.unwrap();
+    assert_eq!(detections[0].language, Language::Spanish);
+
+    let languages = translator.languages().await.unwrap();
+    assert_eq!(languages.len(), Language::all().len());
+}
+
+#[tokio::test]
+async fn pseudo() {
+    let translator: &dyn Translator = &Pseudo::new().expansion(0.0);
+
+    let data = translator
+        .translate(Language::Detect, Language::French, "Hello")
+        .await
+        .unwrap();
+
+    assert_eq!(data.output, "[Ĥéļļö]");
+    assert_eq!(data.source, Language::English);
+    assert_eq!(data.confidence, Some(100.0));
+
+    let results = translator
+        .translate_batch(Language::English, Language::French, &["One", "Two"])
+        .await;
+    assert_eq!(results.len(), 2);
+}